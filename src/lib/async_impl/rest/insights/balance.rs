@@ -4,7 +4,10 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     async_impl::http::client,
-    common::{errors, insights::balance::BalanceItem},
+    common::{
+        errors,
+        insights::balance::{BalanceItem, BalanceWatcher},
+    },
 };
 
 #[derive(Debug)]
@@ -45,4 +48,36 @@ impl<'a> Balance<'a> {
 
         Ok(balance_item)
     }
+
+    /// Gets your account balance and records it in `watcher`, raising alerts for crossed
+    /// thresholds.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::{insights::BalanceWatcher, money::Decimal},
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let watcher = BalanceWatcher::new([Decimal::from(5000), Decimal::from(1000)], |alert| {
+    ///     println!("Balance is down to {}, time to top up.", alert.balance);
+    /// });
+    ///
+    /// client.insights.balance.refresh(&watcher).await.unwrap();
+    ///
+    /// println!("{:?}", watcher.latest());
+    /// # }
+    /// ```
+    pub async fn refresh(
+        &self,
+        watcher: &BalanceWatcher,
+    ) -> Result<BalanceItem, errors::HttpError> {
+        let balance_item = self.get().await?;
+        watcher.observe(&balance_item);
+        Ok(balance_item)
+    }
 }
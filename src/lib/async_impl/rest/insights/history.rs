@@ -1,15 +1,25 @@
 //! Request organization's account messaging history.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
+use futures::{future, stream, stream::BoxStream, StreamExt, TryStreamExt};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
     async_impl::http::client,
     common::{
         errors,
-        insights::history::{HistoryItem, HistoryResponse},
-        pagination,
+        insights::{
+            export::{ExportCursor, ExportSummary, HistoryExporter},
+            history::{DeliveryStatus, HistoryItem, HistoryResponse},
+            query::HistoryQuery,
+        },
+        pagination, MIN_POLL_INTERVAL,
     },
 };
 
@@ -63,6 +73,257 @@ impl<'a> History<'a> {
         let history_items = self._get(page).await?;
         Ok(history_items)
     }
+
+    /// Stream your history a page at a time, newest first, fetching the next page only when the
+    /// previous one has been consumed. The stream ends after the first error.
+    pub fn pages(&self) -> BoxStream<'_, Result<Vec<HistoryItem>, errors::HttpError>> {
+        stream::unfold(Some(1usize), move |page| async move {
+            let page = page?;
+
+            match self._get(page.to_string().as_str()).await {
+                Ok(history_items) if history_items.is_empty() => None,
+                Ok(history_items) => Some((Ok(history_items), Some(page + 1))),
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+        .boxed()
+    }
+
+    /// Stream the messages matching `query`, stopping as soon as the history is past its date
+    /// range or its limit is reached.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::insights::{DeliveryStatus, HistoryQuery},
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let query = HistoryQuery::new()
+    ///     .status(DeliveryStatus::Failed)
+    ///     .since("2024-01-01");
+    ///
+    /// let mut failed = client.insights.history.query_stream(&query);
+    ///
+    /// while let Some(message) = failed.try_next().await.unwrap() {
+    ///     println!("{} {}", message.receiver, message.status);
+    /// }
+    /// # }
+    /// ```
+    pub fn query_stream<'q>(
+        &'q self,
+        query: &'q HistoryQuery,
+    ) -> BoxStream<'q, Result<HistoryItem, errors::HttpError>> {
+        self.pages()
+            .map_ok(|history_items| stream::iter(history_items.into_iter().map(Ok)))
+            .try_flatten()
+            .try_take_while(move |item| future::ready(Ok(!query.is_before_range(item))))
+            .try_filter(move |item| future::ready(query.matches(item)))
+            .take(query.max_matches())
+            .boxed()
+    }
+
+    /// Collect the messages matching `query`, see [`History::query_stream`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{async_impl::rest::termii, common::insights::HistoryQuery};
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let query = HistoryQuery::new()
+    ///     .receiver("2347062668208")
+    ///     .since("2024-01-01")
+    ///     .until("2024-01-31")
+    ///     .limit(50);
+    ///
+    /// let messages = client.insights.history.query(&query).await.unwrap();
+    ///
+    /// println!("{:?}", messages);
+    /// # }
+    /// ```
+    pub async fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryItem>, errors::HttpError> {
+        self.query_stream(query).try_collect().await
+    }
+
+    /// Write your history to `writer` with `exporter`, a page at a time.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::insights::{ExportFormat, HistoryExporter, HistoryQuery},
+    /// };
+    /// use tokio::{fs::File, io::BufWriter};
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let exporter = HistoryExporter::new(ExportFormat::Csv)
+    ///     .query(HistoryQuery::new().since("2024-01-01").until("2024-01-31"));
+    ///
+    /// let mut file = BufWriter::new(File::create("2024-01.csv").await.unwrap());
+    ///
+    /// let summary = client
+    ///     .insights
+    ///     .history
+    ///     .export(&exporter, &mut file).await
+    ///     .unwrap();
+    ///
+    /// // Next month, `.resume_after(summary.cursor.unwrap())` exports only the newer messages.
+    /// println!("Exported {} messages.", summary.exported);
+    /// # }
+    /// ```
+    pub async fn export<W: AsyncWrite + Unpin>(
+        &self,
+        exporter: &HistoryExporter,
+        writer: &mut W,
+    ) -> Result<ExportSummary, errors::ExportError> {
+        let mut buffer = Vec::new();
+        exporter.write_header(&mut buffer)?;
+
+        let mut exported = 0usize;
+        let mut cursor = None;
+        let mut items = self.query_stream(exporter.history_query());
+
+        while let Some(item) = items.try_next().await? {
+            if exporter.is_exported(&item) {
+                break;
+            }
+
+            exporter.write_item(&item, &mut buffer)?;
+            writer.write_all(&buffer).await?;
+            buffer.clear();
+
+            cursor.get_or_insert_with(|| ExportCursor::from(&item));
+            exported += 1;
+        }
+
+        writer.write_all(&buffer).await?;
+        writer.flush().await?;
+
+        Ok(ExportSummary {
+            exported,
+            cursor: cursor.or_else(|| exporter.resumed_from().cloned()),
+        })
+    }
+
+    /// Find a sent message by the `message_id` returned when sending it.
+    ///
+    /// Termii has no lookup by id, so this walks at most `max_pages` of your history from the
+    /// newest page. Recent messages are on the first pages, keep `max_pages` small when polling.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::async_impl::rest::termii;
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let message = client
+    ///     .insights
+    ///     .history
+    ///     .find("3017544054459309654184010", 5).await
+    ///     .unwrap();
+    ///
+    /// if let Some(message) = message {
+    ///     println!("{:?}", message.delivery_status());
+    /// }
+    /// # }
+    /// ```
+    pub async fn find(
+        &self,
+        message_id: &str,
+        max_pages: usize,
+    ) -> Result<Option<HistoryItem>, errors::HttpError> {
+        let mut pages = self.pages().take(max_pages);
+
+        while let Some(history_items) = pages.try_next().await? {
+            if let Some(item) = history_items
+                .into_iter()
+                .find(|item| item.message_id == message_id)
+            {
+                return Ok(Some(item));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the delivery status of a sent message from the first `max_pages` of your history,
+    /// `None` if termii doesn't list the message there yet.
+    pub async fn delivery_status(
+        &self,
+        message_id: &str,
+        max_pages: usize,
+    ) -> Result<Option<DeliveryStatus>, errors::HttpError> {
+        let item = self.find(message_id, max_pages).await?;
+        Ok(item.map(|item| item.delivery_status()))
+    }
+
+    /// Poll the delivery status of a sent message every `interval` until it reaches a
+    /// terminal state or `timeout` elapses, returning the last status seen.
+    ///
+    /// Each poll looks through the first `max_pages` of your history, `interval` is at least
+    /// [`MIN_POLL_INTERVAL`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{async_impl::rest::termii, common::insights::DeliveryStatus};
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let status = client
+    ///     .insights
+    ///     .history
+    ///     .wait_for_delivery(
+    ///         "3017544054459309654184010",
+    ///         5,
+    ///         Duration::from_secs(10),
+    ///         Duration::from_secs(120),
+    ///     ).await
+    ///     .unwrap();
+    ///
+    /// if status == Some(DeliveryStatus::Delivered) {
+    ///     println!("Delivered.");
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_delivery(
+        &self,
+        message_id: &str,
+        max_pages: usize,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<Option<DeliveryStatus>, errors::HttpError> {
+        let interval = interval.max(MIN_POLL_INTERVAL);
+        let started = Instant::now();
+
+        loop {
+            let status = self.delivery_status(message_id, max_pages).await?;
+
+            let done = matches!(&status, Some(status) if status.is_terminal());
+
+            if done || started.elapsed().saturating_add(interval) > timeout {
+                return Ok(status);
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
 }
 
 #[async_trait]
@@ -1,10 +1,8 @@
 use std::sync::Arc;
 
-use crate::{
-    async_impl::{
-        http::client,
-        rest::insights::{Balance, History, Search, Status},
-    },
+use crate::async_impl::{
+    http::client,
+    rest::insights::{Balance, History, Search, Status},
 };
 
 #[derive(Debug)]
@@ -2,9 +2,17 @@
 
 use std::{collections::HashMap, sync::Arc};
 
+use futures::{stream, StreamExt};
+
 use crate::{
     async_impl::http::client,
-    common::{errors, insights::search::SearchItem},
+    common::{
+        errors,
+        insights::{
+            cache::{LookupCache, LookupResult, NoLookupCache},
+            search::SearchItem,
+        },
+    },
 };
 
 #[derive(Debug)]
@@ -46,4 +54,98 @@ impl<'a> Search<'a> {
 
         Ok(search_item)
     }
+
+    /// Verify many phone numbers with at most `concurrency` requests in flight.
+    ///
+    /// Results are returned in the order of `phone_numbers`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::async_impl::rest::termii;
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let results = client
+    ///     .insights
+    ///     .search
+    ///     .get_many(vec!["234XXXXXXXXXX", "234YYYYYYYYYY"], 10).await;
+    ///
+    /// for lookup in results {
+    ///     println!("{}: {:?}", lookup.phone_number, lookup.result);
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_many<I>(
+        &self,
+        phone_numbers: I,
+        concurrency: usize,
+    ) -> Vec<LookupResult<SearchItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.get_many_cached(phone_numbers, concurrency, &NoLookupCache)
+            .await
+    }
+
+    /// Like [`get_many`](Self::get_many), answering from `cache` when it can and filling it
+    /// with successful lookups.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{async_impl::rest::termii, common::insights::InMemoryLookupCache};
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let cache = InMemoryLookupCache::new(Duration::from_secs(24 * 60 * 60));
+    ///
+    /// let results = client
+    ///     .insights
+    ///     .search
+    ///     .get_many_cached(vec!["234XXXXXXXXXX", "234YYYYYYYYYY"], 10, &cache).await;
+    ///
+    /// println!("{:?}", results);
+    /// # }
+    /// ```
+    pub async fn get_many_cached<I, C>(
+        &self,
+        phone_numbers: I,
+        concurrency: usize,
+        cache: &C,
+    ) -> Vec<LookupResult<SearchItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        C: LookupCache<SearchItem> + ?Sized,
+    {
+        stream::iter(phone_numbers.into_iter().map(|n| n.as_ref().to_string()))
+            .map(|phone_number| async move {
+                let result = match cache.get(&phone_number) {
+                    Some(search_item) => Ok(search_item),
+                    None => {
+                        let result = self.get(&phone_number).await;
+
+                        if let Ok(search_item) = &result {
+                            cache.put(&phone_number, search_item.clone());
+                        }
+
+                        result
+                    }
+                };
+
+                LookupResult {
+                    phone_number,
+                    result,
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
 }
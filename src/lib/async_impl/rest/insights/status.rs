@@ -2,9 +2,17 @@
 
 use std::{collections::HashMap, sync::Arc};
 
+use futures::{stream, StreamExt};
+
 use crate::{
     async_impl::http::client,
-    common::{errors, insights::status::StatusItem},
+    common::{
+        errors,
+        insights::{
+            cache::{LookupCache, LookupResult, NoLookupCache},
+            status::StatusItem,
+        },
+    },
 };
 
 #[derive(Debug)]
@@ -53,4 +61,84 @@ impl<'a> Status<'a> {
 
         Ok(status_response)
     }
+
+    /// Detect fake or ported numbers for many phone numbers of the same country with at most
+    /// `concurrency` requests in flight.
+    ///
+    /// Results are returned in the order of `phone_numbers`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::async_impl::rest::termii;
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let results = client
+    ///     .insights
+    ///     .status
+    ///     .get_many(vec!["234XXXXXXXXXX", "234YYYYYYYYYY"], "NG", 10).await;
+    ///
+    /// for lookup in results {
+    ///     if let Ok(status) = lookup.result {
+    ///         println!("{} ported: {}", lookup.phone_number, status.is_ported());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_many<I>(
+        &self,
+        phone_numbers: I,
+        country_code: &str,
+        concurrency: usize,
+    ) -> Vec<LookupResult<StatusItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.get_many_cached(phone_numbers, country_code, concurrency, &NoLookupCache)
+            .await
+    }
+
+    /// Like [`get_many`](Self::get_many), answering from `cache` when it can and filling it
+    /// with successful lookups.
+    pub async fn get_many_cached<I, C>(
+        &self,
+        phone_numbers: I,
+        country_code: &str,
+        concurrency: usize,
+        cache: &C,
+    ) -> Vec<LookupResult<StatusItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        C: LookupCache<StatusItem> + ?Sized,
+    {
+        stream::iter(phone_numbers.into_iter().map(|n| n.as_ref().to_string()))
+            .map(|phone_number| async move {
+                let key = format!("{}:{}", country_code, phone_number);
+
+                let result = match cache.get(&key) {
+                    Some(status_item) => Ok(status_item),
+                    None => {
+                        let result = self.get(&phone_number, country_code).await;
+
+                        if let Ok(status_item) = &result {
+                            cache.put(&key, status_item.clone());
+                        }
+
+                        result
+                    }
+                };
+
+                LookupResult {
+                    phone_number,
+                    result,
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
 }
@@ -2,16 +2,34 @@
 
 use std::sync::Arc;
 
+use futures::{stream, StreamExt};
+
 use crate::{
-    async_impl::http::client,
+    async_impl::{
+        http::client,
+        rest::insights::{Balance, Search},
+    },
     common::{
-        errors,
-        switch::messaging::{
-            MessageBulkRequest, MessageBulkResponse, MessageRequest, MessageResponse,
+        errors::{self, TemplateError},
+        insights::{cache::LookupCache, search::SearchItem},
+        switch::{
+            cost::{CostEstimate, PriceTable},
+            idempotency::{self, IdempotencyStore},
+            messaging::{
+                Channel, MessageBulkRequest, MessageBulkResponse, MessageRequest, MessageResponse,
+                MessageTemplate, MessageType, PersonalizedResponse, RoutedBulkResponse,
+                RoutedMessageResponse,
+            },
+            templates::TemplateVariables,
         },
     },
 };
 
+const DND_LOOKUP_CONCURRENCY: usize = 10;
+const PERSONALIZED_SEND_CONCURRENCY: usize = 10;
+/// Termii accepts at most this many numbers in one bulk request.
+const MAX_BULK_RECIPIENTS: usize = 10_000;
+
 #[derive(Debug)]
 pub struct Messaging<'a> {
     api_key: &'a str,
@@ -109,4 +127,396 @@ impl<'a> Messaging<'a> {
 
         Ok(message_response)
     }
+
+    /// Send a message on the channel that reaches the recipient.
+    ///
+    /// The recipient's DND status is looked up first, generic messages go out on the dnd channel
+    /// when DND is active. If the lookup fails the dnd channel is used, it costs more but delivers.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::{
+    ///         insights::InMemoryLookupCache,
+    ///         switch::messaging::{Channel, MessageRequest, MessageType},
+    ///     },
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let dnd_cache = InMemoryLookupCache::new(Duration::from_secs(24 * 60 * 60));
+    ///
+    /// let message_payload = MessageRequest::new(
+    ///     "234XXXXXXXXXX".to_string(),
+    ///     "Your org sender id".to_string(),
+    ///     "Your message".to_string(),
+    ///     MessageType::Plain,
+    ///     Channel::Generic,
+    /// );
+    ///
+    /// let routed = client
+    ///     .switch
+    ///     .messaging
+    ///     .send_dnd_aware(message_payload, &dnd_cache).await
+    ///     .unwrap();
+    ///
+    /// println!("Sent on {:?}: {:?}", routed.channel, routed.response);
+    /// # }
+    /// ```
+    pub async fn send_dnd_aware<C>(
+        &self,
+        mut message: MessageRequest,
+        cache: &C,
+    ) -> Result<RoutedMessageResponse, errors::HttpError>
+    where
+        C: LookupCache<SearchItem> + ?Sized,
+    {
+        if message.channel.follows_dnd_status() {
+            let search = Search::new(self.api_key, Arc::clone(&self.client));
+
+            let dnd_active = search
+                .get_many_cached([&message.to], 1, cache)
+                .await
+                .into_iter()
+                .next()
+                .map(|lookup| lookup.result.map_or(true, |item| item.dnd_active))
+                .unwrap_or(true);
+
+            message.channel = message.channel.for_dnd_status(dnd_active);
+        }
+
+        let channel = message.channel.clone();
+        let response = self.send(message).await?;
+
+        Ok(RoutedMessageResponse { channel, response })
+    }
+
+    /// Send a message to multiple recipients, splitting them between the generic and dnd
+    /// channels by their DND status.
+    ///
+    /// One bulk request is sent per channel used and per 10,000 recipients, the most termii
+    /// takes at once, see [`send_dnd_aware`](Self::send_dnd_aware). Every request is made even if
+    /// another fails, check the result of each.
+    pub async fn send_bulk_dnd_aware<C>(
+        &self,
+        message: MessageBulkRequest,
+        cache: &C,
+    ) -> Vec<RoutedBulkResponse>
+    where
+        C: LookupCache<SearchItem> + ?Sized,
+    {
+        let groups = if message.channel.follows_dnd_status() {
+            let search = Search::new(self.api_key, Arc::clone(&self.client));
+
+            let mut generic = Vec::new();
+            let mut dnd = Vec::new();
+
+            for lookup in search
+                .get_many_cached(&message.to, DND_LOOKUP_CONCURRENCY, cache)
+                .await
+            {
+                let dnd_active = lookup.result.map_or(true, |item| item.dnd_active);
+
+                match message.channel.for_dnd_status(dnd_active) {
+                    Channel::Dnd => dnd.push(lookup.phone_number),
+                    _ => generic.push(lookup.phone_number),
+                }
+            }
+
+            vec![(Channel::Generic, generic), (Channel::Dnd, dnd)]
+        } else {
+            vec![(message.channel.clone(), message.to.clone())]
+        };
+
+        let mut responses = Vec::new();
+
+        for (channel, to) in groups {
+            for chunk in to.chunks(MAX_BULK_RECIPIENTS) {
+                let request = MessageBulkRequest::new(
+                    chunk.to_vec(),
+                    message.from.clone(),
+                    message.sms.clone(),
+                    message.message_type.clone(),
+                    channel.clone(),
+                );
+
+                let result = self.send_bulk(request).await;
+
+                responses.push(RoutedBulkResponse {
+                    channel: channel.clone(),
+                    to: chunk.to_vec(),
+                    result,
+                });
+            }
+        }
+
+        responses
+    }
+
+    /// Send a message only if its estimated cost is covered by your current balance.
+    ///
+    /// Fails with [`SendError::InsufficientBalance`](errors::SendError::InsufficientBalance)
+    /// without sending otherwise, or with
+    /// [`SendError::CurrencyMismatch`](errors::SendError::CurrencyMismatch) when `prices` are not
+    /// in the currency of your balance.
+    pub async fn send_within_balance(
+        &self,
+        message: MessageRequest,
+        prices: &PriceTable,
+    ) -> Result<MessageResponse, errors::SendError> {
+        self.ensure_balance_covers(prices.estimate(&message))
+            .await?;
+        Ok(self.send(message).await?)
+    }
+
+    /// Send a bulk message only if its estimated cost is covered by your current balance.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::{
+    ///         money::{Currency, Decimal},
+    ///         switch::{
+    ///             cost::PriceTable,
+    ///             messaging::{Channel, MessageBulkRequest, MessageType},
+    ///         },
+    ///     },
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let prices =
+    ///     PriceTable::new(Currency::new("NGN"), Decimal::from(4)).prefix("234803", "MTN");
+    ///
+    /// let message_bulk_payload = MessageBulkRequest::new(
+    ///     vec!["234XXXXXXXXXX".to_string(), "234XXXXXXXXXX".to_string()],
+    ///     "Your org sender id".to_string(),
+    ///     "Your message".to_string(),
+    ///     MessageType::Plain,
+    ///     Channel::Generic,
+    /// );
+    ///
+    /// println!("{:?}", prices.estimate_bulk(&message_bulk_payload));
+    ///
+    /// let message_bulk_response = client
+    ///     .switch
+    ///     .messaging
+    ///     .send_bulk_within_balance(message_bulk_payload, &prices).await;
+    ///
+    /// println!("{:?}", message_bulk_response);
+    /// # }
+    /// ```
+    pub async fn send_bulk_within_balance(
+        &self,
+        message: MessageBulkRequest,
+        prices: &PriceTable,
+    ) -> Result<MessageBulkResponse, errors::SendError> {
+        self.ensure_balance_covers(prices.estimate_bulk(&message))
+            .await?;
+        Ok(self.send_bulk(message).await?)
+    }
+
+    /// Render `template` for every recipient and send the results.
+    ///
+    /// Recipients whose rendered body is the same are sent together as one bulk request, so a
+    /// template without per-recipient variables costs a single request. Nothing is sent if any
+    /// body fails to render.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::switch::{
+    ///         messaging::{Channel, MessageTemplate},
+    ///         templates::TemplateVariables,
+    ///     },
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let template = MessageTemplate::parse("Hi {{name}}, your order shipped.").unwrap();
+    ///
+    /// let recipients = [("234XXXXXXXXXX", "Ada"), ("234YYYYYYYYYY", "Tunde")].map(|(number, name)| {
+    ///     let mut variables = TemplateVariables::new();
+    ///     variables.insert("name".to_string(), name.to_string());
+    ///     (number, variables)
+    /// });
+    ///
+    /// let responses = client
+    ///     .switch
+    ///     .messaging
+    ///     .send_personalized("Your org sender id", Channel::Generic, &template, recipients).await
+    ///     .unwrap();
+    ///
+    /// for response in responses {
+    ///     println!("{:?}: {:?}", response.to, response.result);
+    /// }
+    /// # }
+    /// ```
+    pub async fn send_personalized<I, N>(
+        &self,
+        from: &str,
+        channel: Channel,
+        template: &MessageTemplate,
+        recipients: I,
+    ) -> Result<Vec<PersonalizedResponse>, TemplateError>
+    where
+        I: IntoIterator<Item = (N, TemplateVariables)>,
+        N: Into<String>,
+    {
+        let requests = personalized_requests(from, &channel, template, recipients)?;
+
+        Ok(stream::iter(requests)
+            .map(|request| async move {
+                let sms = request.sms.clone();
+                let to = request.to.clone();
+                let result = self.send_bulk(request).await;
+
+                PersonalizedResponse { sms, to, result }
+            })
+            .buffered(PERSONALIZED_SEND_CONCURRENCY)
+            .collect()
+            .await)
+    }
+
+    /// Send a message unless one was already sent with `key`, returning the stored response then.
+    ///
+    /// Use a key that identifies the message on your side, so a handler retried after a timeout
+    /// doesn't message the recipient twice. A key reused within the store's window returns the
+    /// first response even when the message differs, and fails with
+    /// [`SendError::InFlight`](errors::SendError::InFlight) while the first send is unfinished.
+    /// The key is released when termii rejects the send, but stays reserved after a connection
+    /// error, a server error or an unreadable success response since the message may have gone
+    /// out.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::switch::{
+    ///         idempotency::InMemoryIdempotencyStore,
+    ///         messaging::{Channel, MessageRequest, MessageType},
+    ///     },
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let sent = InMemoryIdempotencyStore::new(Duration::from_secs(24 * 60 * 60));
+    ///
+    /// let message_payload = MessageRequest::new(
+    ///     "234XXXXXXXXXX".to_string(),
+    ///     "YourOrg".to_string(),
+    ///     "Your order 1042 shipped.".to_string(),
+    ///     MessageType::Plain,
+    ///     Channel::Generic,
+    /// );
+    ///
+    /// let message_response = client
+    ///     .switch
+    ///     .messaging
+    ///     .send_idempotent("order-1042-shipped", message_payload, &sent).await;
+    ///
+    /// println!("{:?}", message_response);
+    /// # }
+    /// ```
+    pub async fn send_idempotent<S>(
+        &self,
+        key: &str,
+        message: MessageRequest,
+        store: &S,
+    ) -> Result<MessageResponse, errors::SendError>
+    where
+        S: IdempotencyStore<MessageResponse> + ?Sized,
+    {
+        if let Some(response) = idempotency::begin_send(store, key)? {
+            return Ok(response);
+        }
+
+        idempotency::finish_send(store, key, self.send(message).await)
+    }
+
+    /// Send a bulk message unless one was already sent with `key`, like
+    /// [`send_idempotent`](Self::send_idempotent).
+    pub async fn send_bulk_idempotent<S>(
+        &self,
+        key: &str,
+        message: MessageBulkRequest,
+        store: &S,
+    ) -> Result<MessageBulkResponse, errors::SendError>
+    where
+        S: IdempotencyStore<MessageBulkResponse> + ?Sized,
+    {
+        if let Some(response) = idempotency::begin_send(store, key)? {
+            return Ok(response);
+        }
+
+        idempotency::finish_send(store, key, self.send_bulk(message).await)
+    }
+
+    async fn ensure_balance_covers(&self, estimate: CostEstimate) -> Result<(), errors::SendError> {
+        let balance = Balance::new(self.api_key, Arc::clone(&self.client))
+            .get()
+            .await?
+            .balance;
+
+        if let Some(currency) = &balance.currency {
+            if *currency != estimate.currency {
+                return Err(errors::SendError::CurrencyMismatch {
+                    prices: estimate.currency,
+                    balance: currency.clone(),
+                });
+            }
+        }
+
+        if estimate.total > balance.amount {
+            return Err(errors::SendError::InsufficientBalance {
+                estimated: estimate.total,
+                balance,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Bulk requests for a personalized send, split to stay within the bulk recipient limit.
+fn personalized_requests<I, N>(
+    from: &str,
+    channel: &Channel,
+    template: &MessageTemplate,
+    recipients: I,
+) -> Result<Vec<MessageBulkRequest>, TemplateError>
+where
+    I: IntoIterator<Item = (N, TemplateVariables)>,
+    N: Into<String>,
+{
+    let mut requests = Vec::new();
+
+    for (sms, to) in template.render_grouped(recipients)? {
+        for chunk in to.chunks(MAX_BULK_RECIPIENTS) {
+            requests.push(MessageBulkRequest::new(
+                chunk.to_vec(),
+                from.to_string(),
+                sms.clone(),
+                MessageType::Plain,
+                channel.clone(),
+            ));
+        }
+    }
+
+    Ok(requests)
 }
@@ -1,6 +1,10 @@
 //! Request new Sender Id and retrieve their status.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
 
@@ -9,8 +13,10 @@ use crate::{
     common::{
         errors, pagination,
         switch::sender_id::{
-            SenderIDItem, SenderIDRequest, SenderIDRequestResponse, SenderIDResponse,
+            validate_sender_id, ApprovedSenderIds, SenderIDItem, SenderIDRequest,
+            SenderIDRequestResponse, SenderIDResponse, SenderIdStatus,
         },
+        MIN_POLL_INTERVAL,
     },
 };
 
@@ -101,6 +107,161 @@ impl<'a> SenderID<'a> {
 
         Ok(sender_id_request_response)
     }
+
+    /// Find one of your sender IDs by name, `None` if it was never requested.
+    pub async fn find(&self, name: &str) -> Result<Option<SenderIDItem>, errors::HttpError> {
+        let mut current_page = 1usize;
+
+        loop {
+            let sender_id_items = self._get(current_page.to_string().as_str()).await?;
+
+            if sender_id_items.is_empty() {
+                return Ok(None);
+            }
+
+            if let Some(item) = sender_id_items
+                .into_iter()
+                .find(|item| item.sender_id == name)
+            {
+                return Ok(Some(item));
+            }
+
+            current_page += 1;
+        }
+    }
+
+    /// Poll a requested sender ID until termii approves, blocks or rejects it, or `timeout`
+    /// elapses, returning the last state seen.
+    ///
+    /// The wait between polls starts at `interval` and doubles up to `max_interval`, reviews
+    /// can take days. Both are at least [`MIN_POLL_INTERVAL`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{async_impl::rest::termii, common::switch::sender_id::SenderIdStatus};
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let sender_id = client
+    ///     .switch
+    ///     .sender_id
+    ///     .wait_until_resolved(
+    ///         "OrgNewsLetter",
+    ///         Duration::from_secs(60),
+    ///         Duration::from_secs(60 * 60),
+    ///         Duration::from_secs(3 * 24 * 60 * 60),
+    ///     ).await
+    ///     .unwrap();
+    ///
+    /// match sender_id.map(|item| item.status) {
+    ///     Some(SenderIdStatus::Active) => println!("Approved."),
+    ///     status => println!("Not approved: {:?}", status),
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_until_resolved(
+        &self,
+        name: &str,
+        interval: Duration,
+        max_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Option<SenderIDItem>, errors::HttpError> {
+        let started = Instant::now();
+        let max_interval = max_interval.max(MIN_POLL_INTERVAL);
+        let mut interval = interval.max(MIN_POLL_INTERVAL).min(max_interval);
+
+        loop {
+            let item = self.find(name).await?;
+
+            let done = matches!(&item, Some(item) if item.status.is_resolved());
+
+            if done || started.elapsed().saturating_add(interval) > timeout {
+                return Ok(item);
+            }
+
+            tokio::time::sleep(interval).await;
+
+            interval = interval.saturating_mul(2).min(max_interval);
+        }
+    }
+
+    /// Whether `name` is an approved sender ID of your account, refreshing `approved` from
+    /// [`all`](pagination::PaginatedResourceAsync::all) when it is stale.
+    pub async fn is_approved(
+        &self,
+        name: &str,
+        approved: &ApprovedSenderIds,
+    ) -> Result<bool, errors::HttpError> {
+        if let Some(is_approved) = approved.contains(name) {
+            return Ok(is_approved);
+        }
+
+        let sender_id_items = pagination::PaginatedResourceAsync::all(self).await?;
+        approved.update(&sender_id_items);
+
+        Ok(sender_id_items
+            .iter()
+            .any(|item| item.sender_id == name && item.status == SenderIdStatus::Active))
+    }
+
+    /// Validate `name` and check it is approved, failing with
+    /// [`ValidationError::SenderIdNotApproved`](errors::ValidationError::SenderIdNotApproved)
+    /// before termii gets the chance to reject a send.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::switch::{
+    ///         messaging::{Channel, MessageRequest, MessageType},
+    ///         sender_id::ApprovedSenderIds,
+    ///     },
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let approved = ApprovedSenderIds::new(Duration::from_secs(60 * 60));
+    ///
+    /// let message = MessageRequest::new(
+    ///     "234XXXXXXXXXX".to_string(),
+    ///     "YourOrg".to_string(),
+    ///     "Your message".to_string(),
+    ///     MessageType::Plain,
+    ///     Channel::Generic,
+    /// );
+    ///
+    /// client
+    ///     .switch
+    ///     .sender_id
+    ///     .ensure_approved(&message.from, &approved).await
+    ///     .unwrap();
+    ///
+    /// let message_response = client.switch.messaging.send(message).await;
+    ///
+    /// println!("{:?}", message_response);
+    /// # }
+    /// ```
+    pub async fn ensure_approved(
+        &self,
+        name: &str,
+        approved: &ApprovedSenderIds,
+    ) -> Result<(), errors::SendError> {
+        validate_sender_id(name)?;
+
+        if !self.is_approved(name, approved).await? {
+            return Err(errors::ValidationError::SenderIdNotApproved(name.to_string()).into());
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -2,11 +2,13 @@
 
 use std::sync::Arc;
 
+use serde::Serialize;
+
 use crate::{
     async_impl::http::client,
     common::{
         errors,
-        switch::templates::{TemplateItem, TemplatesRequest},
+        switch::templates::{DeviceTemplate, TemplateItem, TemplatesRequest},
     },
 };
 
@@ -52,9 +54,9 @@ impl<'a> Templates<'a> {
     ///
     /// println!("{:?}", templates_response);
     /// ```
-    pub async fn send<T>(
+    pub async fn send<D: Serialize>(
         &self,
-        mut payload: TemplatesRequest,
+        mut payload: TemplatesRequest<D>,
     ) -> Result<Vec<TemplateItem>, errors::HttpError> {
         payload.set_api_key(self.api_key);
 
@@ -67,4 +69,48 @@ impl<'a> Templates<'a> {
 
         Ok(template)
     }
+
+    /// Send a declared [`DeviceTemplate`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{async_impl::rest::termii, device_template};
+    ///
+    /// device_template! {
+    ///     struct OrderShipped = "1493-csdn3-ns34w-sd3434-dfdf" {
+    ///         customer_name: String,
+    ///         order_id: u64,
+    ///     }
+    /// }
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let order_shipped = OrderShipped {
+    ///     customer_name: "Ada".to_string(),
+    ///     order_id: 1042,
+    /// };
+    ///
+    /// let templates_response = client
+    ///     .switch
+    ///     .templates
+    ///     .send_template("+234XXXXXXXXXX", "talert", &order_shipped).await;
+    ///
+    /// println!("{:?}", templates_response);
+    /// # }
+    /// ```
+    pub async fn send_template<T: DeviceTemplate + ?Sized>(
+        &self,
+        phone_number: &str,
+        device_id: &str,
+        template: &T,
+    ) -> Result<Vec<TemplateItem>, errors::HttpError> {
+        self.send(TemplatesRequest::for_template(
+            phone_number.to_string(),
+            device_id.to_string(),
+            template,
+        ))
+        .await
+    }
 }
@@ -5,8 +5,9 @@ use rest::{insights, switch, token};
 use crate::async_impl::{http::client, rest};
 
 #[derive(Debug)]
-#[allow(dead_code)]
 pub struct Termii<'a> {
+    pub(crate) api_key: &'a str,
+    pub(crate) client: Arc<client::HttpClient>,
     pub token: token::Token<'a>,
     pub insights: insights::Insights<'a>,
     pub switch: switch::Switch<'a>,
@@ -25,7 +26,9 @@ impl<'a> Termii<'a> {
         println!("{}", Arc::strong_count(&http_client));
 
         let termii = Termii {
-            insights: insights, 
+            insights: insights,
+            api_key,
+            client: http_client,
             token: token,
             switch: switch,
         };
@@ -0,0 +1,198 @@
+//! Issue, resend and verify one time tokens without keeping
+//! track of pin ids, cooldowns and attempts yourself.
+
+use crate::{
+    async_impl::rest::token::Token,
+    common::{
+        errors::OtpError,
+        token::{
+            otp_manager::{
+                begin_attempt, finish_attempt, unix_now, Attempt, OtpConfig, OtpDelivery,
+                OtpOutcome, OtpSession, OtpStore,
+            },
+            request::RequestTokenRequest,
+            verify::VerifyTokenRequest,
+            voice_token::VoiceTokenRequest,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub struct OtpManager<'a, S: OtpStore> {
+    token: &'a Token<'a>,
+    template: RequestTokenRequest,
+    store: S,
+    config: OtpConfig,
+}
+
+impl<'a, S: OtpStore> OtpManager<'a, S> {
+    /// Create a manager sending tokens built from `template`.
+    ///
+    /// The `to` field of the template is replaced with the phone number of each user.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     async_impl::rest::{termii, token::OtpManager},
+    ///     common::token::{
+    ///         InMemoryOtpStore, OtpOutcome, RequestTokenChannel, RequestTokenMessageType,
+    ///         RequestTokenPinType, RequestTokenRequest,
+    ///     },
+    /// };
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let template = RequestTokenRequest::new(
+    ///     RequestTokenMessageType::NUMERIC,
+    ///     String::new(),
+    ///     String::from("Your org sender ID"),
+    ///     RequestTokenChannel::Generic,
+    ///     3 as u8,
+    ///     10 as usize,
+    ///     6 as u8,
+    ///     String::from("< 1234 >"),
+    ///     String::from("Your pin is < 1234 >"),
+    ///     RequestTokenPinType::NUMERIC,
+    /// );
+    ///
+    /// let otp = OtpManager::new(&client.token, template, InMemoryOtpStore::new());
+    ///
+    /// otp.issue("user-42", "234XXXXXXXXXX").await.unwrap();
+    ///
+    /// match otp.verify("user-42", "123456").await.unwrap() {
+    ///     OtpOutcome::Verified => println!("Welcome back."),
+    ///     outcome => println!("{:?}", outcome),
+    /// }
+    /// # }
+    /// ```
+    pub fn new(token: &'a Token<'a>, template: RequestTokenRequest, store: S) -> OtpManager<'a, S> {
+        OtpManager {
+            token,
+            template,
+            store,
+            config: OtpConfig::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: OtpConfig) -> OtpManager<'a, S> {
+        self.config = config;
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Send a new token to `phone_number` and remember it under `user_key`.
+    ///
+    /// While the user's previous token is still valid this counts as a resend, so it is held to
+    /// the same cooldown and resend limit.
+    pub async fn issue(&self, user_key: &str, phone_number: &str) -> Result<OtpSession, OtpError> {
+        let now = unix_now();
+
+        let resends = match self.store.get(user_key)? {
+            Some(session) if !session.is_expired(now) => {
+                self.check_resend(&session, now)?;
+                session.resends + 1
+            }
+            _ => 0,
+        };
+
+        self.send(user_key, phone_number, resends).await
+    }
+
+    /// Send a fresh token to the user, moving on to the next fallback channel.
+    pub async fn resend(&self, user_key: &str) -> Result<OtpSession, OtpError> {
+        let session = self
+            .store
+            .get(user_key)?
+            .ok_or_else(|| OtpError::NotIssued(user_key.to_string()))?;
+
+        self.check_resend(&session, unix_now())?;
+
+        self.send(user_key, &session.phone_number, session.resends + 1)
+            .await
+    }
+
+    /// Check the pin entered by the user.
+    ///
+    /// The attempt is counted before the pin is sent to termii, so parallel guesses can't get
+    /// past `pin_attempts`. An attempt that fails to reach termii still counts.
+    pub async fn verify(&self, user_key: &str, pin: &str) -> Result<OtpOutcome, OtpError> {
+        let session = match begin_attempt(&self.store, user_key)? {
+            Attempt::Counted(session) => session,
+            Attempt::Refused(outcome) => return Ok(outcome),
+        };
+
+        let request = VerifyTokenRequest::new(session.pin_id.clone(), pin.to_string());
+
+        let outcome: OtpOutcome = self.token.verify_token.send(request).await?.into();
+
+        finish_attempt(&self.store, user_key, &session, outcome)
+    }
+
+    /// Forget the token issued to the user.
+    pub fn expire(&self, user_key: &str) -> Result<(), OtpError> {
+        self.store.remove(user_key)
+    }
+
+    async fn send(
+        &self,
+        user_key: &str,
+        phone_number: &str,
+        resends: u8,
+    ) -> Result<OtpSession, OtpError> {
+        let channel = self.config.delivery_for(&self.template.channel, resends);
+
+        let pin_id = match &channel {
+            OtpDelivery::Message(message_channel) => {
+                let mut payload = self.template.clone();
+                payload.to = phone_number.to_string();
+                payload.channel = message_channel.clone();
+
+                self.token.request_token.send(payload).await?.pin_id
+            }
+            OtpDelivery::Voice => {
+                let payload = VoiceTokenRequest::new(
+                    phone_number.to_string(),
+                    self.template.pin_attempts,
+                    self.template.pin_time_to_live,
+                    self.template.pin_length,
+                );
+
+                self.token.voice_token.send(payload).await?.pin_id
+            }
+        };
+
+        let now = unix_now();
+
+        let session = OtpSession {
+            pin_id,
+            phone_number: phone_number.to_string(),
+            channel,
+            sent_at: now,
+            expires_at: now + self.template.pin_time_to_live as u64 * 60,
+            attempts: 0,
+            max_attempts: self.template.pin_attempts,
+            resends,
+        };
+
+        self.store.put(user_key, session.clone())?;
+
+        Ok(session)
+    }
+
+    fn check_resend(&self, session: &OtpSession, now: u64) -> Result<(), OtpError> {
+        if let Some(retry_after) = session.cooldown_remaining(now, self.config.resend_cooldown) {
+            return Err(OtpError::CooldownActive { retry_after });
+        }
+
+        if session.resends >= self.config.max_resends {
+            return Err(OtpError::ResendLimitReached(self.config.max_resends));
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::async_impl::{
     http::client,
-    rest::token::{InAppToken, RequestToken, VerifyToken},
+    rest::token::{InAppToken, RequestToken, VerifyToken, VoiceToken},
 };
 
 #[derive(Debug)]
@@ -10,6 +10,7 @@ pub struct Token<'a> {
     pub request_token: RequestToken<'a>,
     pub verify_token: VerifyToken<'a>,
     pub in_app_token: InAppToken<'a>,
+    pub voice_token: VoiceToken<'a>,
 }
 
 impl<'a> Token<'a> {
@@ -17,11 +18,13 @@ impl<'a> Token<'a> {
         let request_token = RequestToken::new(api_key, Arc::clone(&client));
         let verify_token = VerifyToken::new(api_key, Arc::clone(&client));
         let in_app_token = InAppToken::new(api_key, Arc::clone(&client));
+        let voice_token = VoiceToken::new(api_key, Arc::clone(&client));
 
         Token {
             request_token,
             verify_token,
             in_app_token,
+            voice_token,
         }
     }
 }
@@ -7,7 +7,7 @@ use crate::{
     async_impl::http::client,
     common::{
         errors,
-        token::verify::{VerifyTokenRequest, VerifyTokenResponse},
+        token::verify::{VerificationOutcome, VerifyTokenRequest},
     },
 };
 
@@ -22,12 +22,15 @@ impl<'a> VerifyToken<'a> {
         VerifyToken { api_key, client }
     }
 
-    /// Verify one-time passwords and return responses.
+    /// Verify one-time passwords and return the outcome.
     ///
     /// ## Examples
     ///
     /// ```rust
-    /// use termii_rust::{async_impl::rest::termii, common::token::VerifyTokenRequest};
+    /// use termii_rust::{
+    ///     async_impl::rest::termii,
+    ///     common::token::{VerificationOutcome, VerifyTokenRequest},
+    /// };
     ///
     /// let client = termii::Termii::new("Your API key");
     ///
@@ -36,14 +39,18 @@ impl<'a> VerifyToken<'a> {
     ///     "195558".to_string(),
     /// );
     ///
-    /// let response = client.token.verify.send(verify_otp_request).await.unwrap();
+    /// let outcome = client.token.verify.send(verify_otp_request).await.unwrap();
     ///
-    /// println!("{:#?}", response);
+    /// match outcome {
+    ///     VerificationOutcome::Verified { msisdn, .. } => println!("{} verified.", msisdn),
+    ///     VerificationOutcome::Expired => println!("The pin has expired, request a new one."),
+    ///     outcome => println!("{:?}", outcome),
+    /// }
     /// ```
     pub async fn send(
         &self,
         mut otp_payload: VerifyTokenRequest,
-    ) -> Result<VerifyTokenResponse, errors::HttpError> {
+    ) -> Result<VerificationOutcome, errors::HttpError> {
         otp_payload.set_api_key(self.api_key);
 
         let response = self
@@ -51,8 +58,15 @@ impl<'a> VerifyToken<'a> {
             .post("sms/otp/verify", None, None, Some(otp_payload))
             .await?;
 
-        let otp_response = response_or_error_text_async!(response, VerifyTokenResponse);
+        let status_code = response.status();
+        let response_text = response.text().await?;
 
-        Ok(otp_response)
+        match VerificationOutcome::from_response_text(status_code.as_u16(), &response_text) {
+            Some(outcome) => Ok(outcome),
+            None => Err(errors::HttpError::JsonError {
+                status: status_code.as_u16() as usize,
+                message: response_text,
+            }),
+        }
     }
 }
@@ -0,0 +1,61 @@
+//! Voice tokens are pins read out to the user in a call, for numbers that can't
+//! receive the token by message.
+
+use std::sync::Arc;
+
+use crate::{
+    async_impl::http::client,
+    common::{
+        errors,
+        token::voice_token::{VoiceTokenRequest, VoiceTokenResponse},
+    },
+};
+
+#[derive(Debug)]
+pub struct VoiceToken<'a> {
+    api_key: &'a str,
+    client: Arc<client::HttpClient>,
+}
+
+impl<'a> VoiceToken<'a> {
+    pub(crate) fn new(api_key: &'a str, client: Arc<client::HttpClient>) -> VoiceToken<'a> {
+        VoiceToken { api_key, client }
+    }
+
+    /// Call the user and read out a pin, verify it with [`VerifyToken`](super::VerifyToken).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{async_impl::rest::termii, common::token::VoiceTokenRequest};
+    ///
+    /// # async fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let voice_token_request = VoiceTokenRequest::new("234XXXXXXXXXX".to_string(), 3, 5, 6);
+    ///
+    /// let voice_token_response = client
+    ///     .token
+    ///     .voice_token
+    ///     .send(voice_token_request).await
+    ///     .unwrap();
+    ///
+    /// println!("{:?}", voice_token_response.pin_id);
+    /// # }
+    /// ```
+    pub async fn send(
+        &self,
+        mut otp_payload: VoiceTokenRequest,
+    ) -> Result<VoiceTokenResponse, errors::HttpError> {
+        otp_payload.set_api_key(self.api_key);
+
+        let response = self
+            .client
+            .post("sms/otp/send/voice", None, None, Some(otp_payload))
+            .await?;
+
+        let voice_token_response = response_or_error_text_async!(response, VoiceTokenResponse);
+
+        Ok(voice_token_response)
+    }
+}
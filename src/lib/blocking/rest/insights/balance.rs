@@ -4,7 +4,10 @@ use std::{collections::HashMap, rc::Rc};
 
 use crate::{
     blocking::http::client,
-    common::{errors, insights::balance::BalanceItem},
+    common::{
+        errors,
+        insights::balance::{BalanceItem, BalanceWatcher},
+    },
 };
 
 #[derive(Debug)]
@@ -44,4 +47,33 @@ impl<'a> Balance<'a> {
 
         Ok(balance_item)
     }
+
+    /// Gets your account balance and records it in `watcher`, raising alerts for crossed
+    /// thresholds.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     blocking::rest::termii,
+    ///     common::{insights::BalanceWatcher, money::Decimal},
+    /// };
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let watcher = BalanceWatcher::new([Decimal::from(5000), Decimal::from(1000)], |alert| {
+    ///     println!("Balance is down to {}, time to top up.", alert.balance);
+    /// });
+    ///
+    /// client.insights.balance.refresh(&watcher).unwrap();
+    ///
+    /// println!("{:?}", watcher.latest());
+    /// # }
+    /// ```
+    pub fn refresh(&self, watcher: &BalanceWatcher) -> Result<BalanceItem, errors::HttpError> {
+        let balance_item = self.get()?;
+        watcher.observe(&balance_item);
+        Ok(balance_item)
+    }
 }
@@ -1,13 +1,23 @@
 //! Request organization's account messaging history.
 
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::HashMap,
+    io, iter,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
     blocking::http::client,
     common::{
         errors,
-        insights::history::{HistoryItem, HistoryResponse},
-        pagination,
+        insights::{
+            export::{ExportCursor, ExportSummary, HistoryExporter},
+            history::{DeliveryStatus, HistoryItem, HistoryResponse},
+            query::HistoryQuery,
+        },
+        pagination, MIN_POLL_INTERVAL,
     },
 };
 
@@ -60,6 +70,257 @@ impl<'a> History<'a> {
         let history_items = self._get(page)?;
         Ok(history_items)
     }
+
+    /// Iterate over your history a page at a time, newest first, fetching the next page only
+    /// when the previous one has been consumed. The iterator ends after the first error.
+    pub fn pages(
+        &self,
+    ) -> Box<dyn Iterator<Item = Result<Vec<HistoryItem>, errors::HttpError>> + '_> {
+        let mut next_page = Some(1usize);
+
+        Box::new(iter::from_fn(move || {
+            let page = next_page.take()?;
+
+            match self._get(page.to_string().as_str()) {
+                Ok(history_items) if history_items.is_empty() => None,
+                Ok(history_items) => {
+                    next_page = Some(page + 1);
+                    Some(Ok(history_items))
+                }
+                Err(err) => Some(Err(err)),
+            }
+        }))
+    }
+
+    /// Iterate over the messages matching `query`, stopping as soon as the history is past its
+    /// date range or its limit is reached.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{
+    ///     blocking::rest::termii,
+    ///     common::insights::{DeliveryStatus, HistoryQuery},
+    /// };
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let query = HistoryQuery::new()
+    ///     .status(DeliveryStatus::Failed)
+    ///     .since("2024-01-01");
+    ///
+    /// for message in client.insights.history.query_iter(&query) {
+    ///     let message = message.unwrap();
+    ///     println!("{} {}", message.receiver, message.status);
+    /// }
+    /// # }
+    /// ```
+    pub fn query_iter<'q>(
+        &'q self,
+        query: &'q HistoryQuery,
+    ) -> Box<dyn Iterator<Item = Result<HistoryItem, errors::HttpError>> + 'q> {
+        let items = self.pages().flat_map(|page| match page {
+            Ok(history_items) => history_items.into_iter().map(Ok).collect::<Vec<_>>(),
+            Err(err) => vec![Err(err)],
+        });
+
+        Box::new(
+            items
+                .take_while(move |item| !matches!(item, Ok(item) if query.is_before_range(item)))
+                .filter(move |item| !matches!(item, Ok(item) if !query.matches(item)))
+                .take(query.max_matches()),
+        )
+    }
+
+    /// Collect the messages matching `query`, see [`History::query_iter`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{blocking::rest::termii, common::insights::HistoryQuery};
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let query = HistoryQuery::new()
+    ///     .receiver("2347062668208")
+    ///     .since("2024-01-01")
+    ///     .until("2024-01-31")
+    ///     .limit(50);
+    ///
+    /// let messages = client.insights.history.query(&query).unwrap();
+    ///
+    /// println!("{:?}", messages);
+    /// # }
+    /// ```
+    pub fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryItem>, errors::HttpError> {
+        self.query_iter(query).collect()
+    }
+
+    /// Write your history to `writer` with `exporter`, a page at a time.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::{fs::File, io::BufWriter};
+    ///
+    /// use termii_rust::{
+    ///     blocking::rest::termii,
+    ///     common::insights::{ExportFormat, HistoryExporter, HistoryQuery},
+    /// };
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let exporter = HistoryExporter::new(ExportFormat::JsonLines)
+    ///     .query(HistoryQuery::new().since("2024-01-01").until("2024-01-31"));
+    ///
+    /// let mut file = BufWriter::new(File::create("2024-01.jsonl").unwrap());
+    ///
+    /// let summary = client.insights.history.export(&exporter, &mut file).unwrap();
+    ///
+    /// // Next month, `.resume_after(summary.cursor.unwrap())` exports only the newer messages.
+    /// println!("Exported {} messages.", summary.exported);
+    /// # }
+    /// ```
+    pub fn export<W: io::Write>(
+        &self,
+        exporter: &HistoryExporter,
+        writer: &mut W,
+    ) -> Result<ExportSummary, errors::ExportError> {
+        exporter.write_header(writer)?;
+
+        let mut exported = 0usize;
+        let mut cursor = None;
+
+        for item in self.query_iter(exporter.history_query()) {
+            let item = item?;
+
+            if exporter.is_exported(&item) {
+                break;
+            }
+
+            exporter.write_item(&item, writer)?;
+
+            cursor.get_or_insert_with(|| ExportCursor::from(&item));
+            exported += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(ExportSummary {
+            exported,
+            cursor: cursor.or_else(|| exporter.resumed_from().cloned()),
+        })
+    }
+
+    /// Find a sent message by the `message_id` returned when sending it.
+    ///
+    /// Termii has no lookup by id, so this walks at most `max_pages` of your history from the
+    /// newest page. Recent messages are on the first pages, keep `max_pages` small when polling.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::blocking::rest::termii;
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let message = client
+    ///     .insights
+    ///     .history
+    ///     .find("3017544054459309654184010", 5)
+    ///     .unwrap();
+    ///
+    /// if let Some(message) = message {
+    ///     println!("{:?}", message.delivery_status());
+    /// }
+    /// # }
+    /// ```
+    pub fn find(
+        &self,
+        message_id: &str,
+        max_pages: usize,
+    ) -> Result<Option<HistoryItem>, errors::HttpError> {
+        for history_items in self.pages().take(max_pages) {
+            if let Some(item) = history_items?
+                .into_iter()
+                .find(|item| item.message_id == message_id)
+            {
+                return Ok(Some(item));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the delivery status of a sent message from the first `max_pages` of your history,
+    /// `None` if termii doesn't list the message there yet.
+    pub fn delivery_status(
+        &self,
+        message_id: &str,
+        max_pages: usize,
+    ) -> Result<Option<DeliveryStatus>, errors::HttpError> {
+        let item = self.find(message_id, max_pages)?;
+        Ok(item.map(|item| item.delivery_status()))
+    }
+
+    /// Poll the delivery status of a sent message every `interval` until it reaches a
+    /// terminal state or `timeout` elapses, returning the last status seen.
+    ///
+    /// Each poll looks through the first `max_pages` of your history, `interval` is at least
+    /// [`MIN_POLL_INTERVAL`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{blocking::rest::termii, common::insights::DeliveryStatus};
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let status = client
+    ///     .insights
+    ///     .history
+    ///     .wait_for_delivery(
+    ///         "3017544054459309654184010",
+    ///         5,
+    ///         Duration::from_secs(10),
+    ///         Duration::from_secs(120),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// if status == Some(DeliveryStatus::Delivered) {
+    ///     println!("Delivered.");
+    /// }
+    /// # }
+    /// ```
+    pub fn wait_for_delivery(
+        &self,
+        message_id: &str,
+        max_pages: usize,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<Option<DeliveryStatus>, errors::HttpError> {
+        let interval = interval.max(MIN_POLL_INTERVAL);
+        let started = Instant::now();
+
+        loop {
+            let status = self.delivery_status(message_id, max_pages)?;
+
+            let done = matches!(&status, Some(status) if status.is_terminal());
+
+            if done || started.elapsed().saturating_add(interval) > timeout {
+                return Ok(status);
+            }
+
+            thread::sleep(interval);
+        }
+    }
 }
 
 impl pagination::PaginatedResource for History<'_> {
@@ -4,7 +4,13 @@ use std::{collections::HashMap, rc::Rc};
 
 use crate::{
     blocking::http::client,
-    common::{errors, insights::search::SearchItem},
+    common::{
+        errors,
+        insights::{
+            cache::{LookupCache, LookupResult, NoLookupCache},
+            search::SearchItem,
+        },
+    },
 };
 
 #[derive(Debug)]
@@ -45,4 +51,93 @@ impl<'a> Search<'a> {
 
         Ok(search_item)
     }
+
+    /// Verify many phone numbers one after the other.
+    ///
+    /// Results are returned in the order of `phone_numbers`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::blocking::rest::termii;
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let results = client
+    ///     .insights
+    ///     .search
+    ///     .get_many(vec!["234XXXXXXXXXX", "234YYYYYYYYYY"]);
+    ///
+    /// for lookup in results {
+    ///     println!("{}: {:?}", lookup.phone_number, lookup.result);
+    /// }
+    /// # }
+    /// ```
+    pub fn get_many<I>(&self, phone_numbers: I) -> Vec<LookupResult<SearchItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.get_many_cached(phone_numbers, &NoLookupCache)
+    }
+
+    /// Like [`get_many`](Self::get_many), answering from `cache` when it can and filling it
+    /// with successful lookups.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::{blocking::rest::termii, common::insights::InMemoryLookupCache};
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let cache = InMemoryLookupCache::new(Duration::from_secs(24 * 60 * 60));
+    ///
+    /// let results = client
+    ///     .insights
+    ///     .search
+    ///     .get_many_cached(vec!["234XXXXXXXXXX", "234YYYYYYYYYY"], &cache);
+    ///
+    /// println!("{:?}", results);
+    /// # }
+    /// ```
+    pub fn get_many_cached<I, C>(
+        &self,
+        phone_numbers: I,
+        cache: &C,
+    ) -> Vec<LookupResult<SearchItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        C: LookupCache<SearchItem> + ?Sized,
+    {
+        phone_numbers
+            .into_iter()
+            .map(|phone_number| {
+                let phone_number = phone_number.as_ref().to_string();
+
+                let result = match cache.get(&phone_number) {
+                    Some(search_item) => Ok(search_item),
+                    None => {
+                        let result = self.get(&phone_number);
+
+                        if let Ok(search_item) = &result {
+                            cache.put(&phone_number, search_item.clone());
+                        }
+
+                        result
+                    }
+                };
+
+                LookupResult {
+                    phone_number,
+                    result,
+                }
+            })
+            .collect()
+    }
 }
@@ -4,7 +4,13 @@ use std::{collections::HashMap, rc::Rc};
 
 use crate::{
     blocking::http::client,
-    common::{errors, insights::status::StatusItem},
+    common::{
+        errors,
+        insights::{
+            cache::{LookupCache, LookupResult, NoLookupCache},
+            status::StatusItem,
+        },
+    },
 };
 
 #[derive(Debug)]
@@ -52,4 +58,77 @@ impl<'a> Status<'a> {
 
         Ok(status_response)
     }
+
+    /// Detect fake or ported numbers for many phone numbers of the same country one after the
+    /// other.
+    ///
+    /// Results are returned in the order of `phone_numbers`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::blocking::rest::termii;
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let results = client
+    ///     .insights
+    ///     .status
+    ///     .get_many(vec!["234XXXXXXXXXX", "234YYYYYYYYYY"], "NG");
+    ///
+    /// for lookup in results {
+    ///     if let Ok(status) = lookup.result {
+    ///         println!("{} ported: {}", lookup.phone_number, status.is_ported());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn get_many<I>(&self, phone_numbers: I, country_code: &str) -> Vec<LookupResult<StatusItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.get_many_cached(phone_numbers, country_code, &NoLookupCache)
+    }
+
+    /// Like [`get_many`](Self::get_many), answering from `cache` when it can and filling it
+    /// with successful lookups.
+    pub fn get_many_cached<I, C>(
+        &self,
+        phone_numbers: I,
+        country_code: &str,
+        cache: &C,
+    ) -> Vec<LookupResult<StatusItem>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        C: LookupCache<StatusItem> + ?Sized,
+    {
+        phone_numbers
+            .into_iter()
+            .map(|phone_number| {
+                let phone_number = phone_number.as_ref().to_string();
+                let key = format!("{}:{}", country_code, phone_number);
+
+                let result = match cache.get(&key) {
+                    Some(status_item) => Ok(status_item),
+                    None => {
+                        let result = self.get(&phone_number, country_code);
+
+                        if let Ok(status_item) = &result {
+                            cache.put(&key, status_item.clone());
+                        }
+
+                        result
+                    }
+                };
+
+                LookupResult {
+                    phone_number,
+                    result,
+                }
+            })
+            .collect()
+    }
 }
@@ -7,6 +7,7 @@
 //! - [`insights`](crate::blocking::rest::insights) :  Retrieve balance, search phone number, retrieve phone number status and view message history.
 
 pub mod insights;
+pub mod outbox;
 pub mod switch;
 pub mod termii;
 pub mod token;
@@ -0,0 +1,216 @@
+//! Send queued requests in the background, retrying until termii accepts them.
+
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    blocking::{
+        http::client,
+        rest::{switch::messaging::Messaging, termii::Termii, token::request::RequestToken},
+    },
+    common::{
+        errors::{HttpError, OutboxError},
+        outbox::{
+            unix_now_millis, JobId, OutboxJob, OutboxPayload, OutboxResponse, OutboxStore,
+            RetryPolicy,
+        },
+    },
+};
+
+/// Jobs sent per call to [`OutboxWorker::process_due`].
+const BATCH_SIZE: usize = 100;
+
+/// Sends the jobs of an [`OutboxStore`].
+///
+/// Transient failures are retried with [`RetryPolicy`] backoff, everything else and jobs that
+/// run out of attempts are dead-lettered. A retried request may already have been accepted by
+/// termii, so jobs are sent at least once rather than exactly once.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use termii_rust::{
+///     blocking::rest::{outbox::OutboxWorker, termii},
+///     common::{
+///         outbox::InMemoryOutboxStore,
+///         switch::messaging::{Channel, MessageRequest, MessageType},
+///     },
+/// };
+///
+/// # fn run() {
+/// let client = termii::Termii::new("Your API key");
+///
+/// let outbox = OutboxWorker::new(&client, InMemoryOutboxStore::new());
+///
+/// let job_id = outbox
+///     .enqueue(MessageRequest::new(
+///         "234XXXXXXXXXX".to_string(),
+///         "YourOrg".to_string(),
+///         "Your message".to_string(),
+///         MessageType::Plain,
+///         Channel::Generic,
+///     ))
+///     .unwrap();
+///
+/// // Or `outbox.run(Duration::from_secs(1))` to keep sending from this thread.
+/// outbox.process_due().unwrap();
+///
+/// println!("{:?}", outbox.response(job_id).unwrap());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct OutboxWorker<S: OutboxStore> {
+    api_key: String,
+    client: Rc<client::HttpClient>,
+    store: S,
+    policy: RetryPolicy,
+    /// Jobs that were sent but the store failed to mark as sent, kept so they aren't sent again.
+    unrecorded: Mutex<HashMap<JobId, (u32, OutboxResponse)>>,
+    last_store_error: Mutex<Option<String>>,
+}
+
+impl<S: OutboxStore> OutboxWorker<S> {
+    pub fn new(termii: &Termii, store: S) -> OutboxWorker<S> {
+        OutboxWorker::with_policy(termii, store, RetryPolicy::default())
+    }
+
+    pub fn with_policy(termii: &Termii, store: S, policy: RetryPolicy) -> OutboxWorker<S> {
+        OutboxWorker {
+            api_key: termii.api_key.to_string(),
+            client: Rc::clone(&termii.client),
+            store,
+            policy,
+            unrecorded: Mutex::new(HashMap::new()),
+            last_store_error: Mutex::new(None),
+        }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Queue a message, bulk message or token request to be sent.
+    pub fn enqueue<P: Into<OutboxPayload>>(&self, payload: P) -> Result<JobId, OutboxError> {
+        self.store.enqueue(payload.into(), unix_now_millis())
+    }
+
+    pub fn job(&self, id: JobId) -> Result<Option<OutboxJob>, OutboxError> {
+        self.store.get(id)
+    }
+
+    /// Termii's response to a job, `None` until it has been sent.
+    pub fn response(&self, id: JobId) -> Result<Option<OutboxResponse>, OutboxError> {
+        Ok(self.store.get(id)?.and_then(|job| job.response().cloned()))
+    }
+
+    /// The last error the store returned while the worker was recording an attempt.
+    pub fn last_store_error(&self) -> Option<String> {
+        lock(&self.last_store_error).clone()
+    }
+
+    /// Send the jobs that are due, returning how many were attempted.
+    ///
+    /// A failure to record an attempt doesn't stop the batch, it is kept in
+    /// [`last_store_error`](Self::last_store_error). A job that was sent is not sent again
+    /// while its response waits to be recorded.
+    pub fn process_due(&self) -> Result<usize, OutboxError> {
+        self.record_unrecorded();
+
+        let jobs = self.store.due(unix_now_millis(), BATCH_SIZE)?;
+        let mut attempted = 0;
+
+        for job in jobs {
+            if lock(&self.unrecorded).contains_key(&job.id) {
+                continue;
+            }
+
+            attempted += 1;
+            let attempts = job.attempts + 1;
+
+            let recorded = match self.send(job.payload) {
+                Ok(response) => match self.store.mark_sent(job.id, attempts, response.clone()) {
+                    Ok(()) => Ok(()),
+                    Err(err) => {
+                        lock(&self.unrecorded).insert(job.id, (attempts, response));
+                        Err(err)
+                    }
+                },
+                Err(err) if err.is_transient() && attempts < self.policy.max_attempts => {
+                    let next_attempt_at =
+                        unix_now_millis() + self.policy.backoff(attempts).as_millis() as u64;
+
+                    self.store
+                        .retry_at(job.id, attempts, next_attempt_at, &err.to_string())
+                }
+                Err(err) => self.store.dead_letter(job.id, attempts, &err.to_string()),
+            };
+
+            if let Err(err) = recorded {
+                self.store_failed(err);
+            }
+        }
+
+        Ok(attempted)
+    }
+
+    /// Process due jobs every `poll_interval`, blocking the thread.
+    ///
+    /// Store errors don't stop the worker, the last one is kept in
+    /// [`last_store_error`](Self::last_store_error) and the jobs are picked up on a later poll.
+    pub fn run(&self, poll_interval: Duration) {
+        loop {
+            let attempted = match self.process_due() {
+                Ok(attempted) => attempted,
+                Err(err) => {
+                    self.store_failed(err);
+                    0
+                }
+            };
+
+            if attempted < BATCH_SIZE {
+                thread::sleep(poll_interval);
+            }
+        }
+    }
+
+    /// Retry recording the responses of sent jobs the store failed to mark as sent.
+    fn record_unrecorded(&self) {
+        let unrecorded = std::mem::take(&mut *lock(&self.unrecorded));
+
+        for (id, (attempts, response)) in unrecorded {
+            if let Err(err) = self.store.mark_sent(id, attempts, response.clone()) {
+                lock(&self.unrecorded).insert(id, (attempts, response));
+                self.store_failed(err);
+            }
+        }
+    }
+
+    fn store_failed(&self, err: OutboxError) {
+        *lock(&self.last_store_error) = Some(err.to_string());
+    }
+
+    fn send(&self, payload: OutboxPayload) -> Result<OutboxResponse, HttpError> {
+        let messaging = Messaging::new(&self.api_key, Rc::clone(&self.client));
+
+        Ok(match payload {
+            OutboxPayload::Message(message) => OutboxResponse::Message(messaging.send(message)?),
+            OutboxPayload::Bulk(message) => OutboxResponse::Bulk(messaging.send_bulk(message)?),
+            OutboxPayload::Token(request) => {
+                let request_token = RequestToken::new(&self.api_key, Rc::clone(&self.client));
+
+                OutboxResponse::Token(request_token.send(request)?)
+            }
+        })
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -2,11 +2,13 @@
 
 use std::rc::Rc;
 
+use serde::Serialize;
+
 use crate::{
     blocking::http::client,
     common::{
         errors,
-        switch::templates::{TemplateItem, TemplatesRequest},
+        switch::templates::{DeviceTemplate, TemplateItem, TemplatesRequest},
     },
 };
 
@@ -47,9 +49,9 @@ impl<'a> Templates<'a> {
     ///
     /// println!("{:?}", templates_response);
     /// ```
-    pub fn send<T>(
+    pub fn send<D: Serialize>(
         &self,
-        mut payload: TemplatesRequest,
+        mut payload: TemplatesRequest<D>,
     ) -> Result<Vec<TemplateItem>, errors::HttpError> {
         payload.set_api_key(self.api_key);
 
@@ -61,4 +63,47 @@ impl<'a> Templates<'a> {
 
         Ok(template)
     }
+
+    /// Send a declared [`DeviceTemplate`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{blocking::rest::termii, device_template};
+    ///
+    /// device_template! {
+    ///     struct OrderShipped = "1493-csdn3-ns34w-sd3434-dfdf" {
+    ///         customer_name: String,
+    ///         order_id: u64,
+    ///     }
+    /// }
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let order_shipped = OrderShipped {
+    ///     customer_name: "Ada".to_string(),
+    ///     order_id: 1042,
+    /// };
+    ///
+    /// let templates_response = client
+    ///     .switch
+    ///     .templates
+    ///     .send_template("+234XXXXXXXXXX", "talert", &order_shipped);
+    ///
+    /// println!("{:?}", templates_response);
+    /// # }
+    /// ```
+    pub fn send_template<T: DeviceTemplate + ?Sized>(
+        &self,
+        phone_number: &str,
+        device_id: &str,
+        template: &T,
+    ) -> Result<Vec<TemplateItem>, errors::HttpError> {
+        self.send(TemplatesRequest::for_template(
+            phone_number.to_string(),
+            device_id.to_string(),
+            template,
+        ))
+    }
 }
@@ -6,8 +6,8 @@ use crate::blocking::{http::client, rest};
 
 #[derive(Debug)]
 pub struct Termii<'a> {
-    api_key: &'a str,
-    client: Rc<client::HttpClient>,
+    pub(crate) api_key: &'a str,
+    pub(crate) client: Rc<client::HttpClient>,
     pub token: token::Token<'a>,
     pub insights: insights::Insights<'a>,
     pub switch: switch::Switch<'a>,
@@ -86,3 +86,9 @@ pub use verify::*;
 
 pub mod in_app_token;
 pub use in_app_token::*;
+
+pub mod voice_token;
+pub use voice_token::*;
+
+pub mod otp_manager;
+pub use otp_manager::*;
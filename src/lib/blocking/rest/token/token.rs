@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use crate::blocking::{
     http::client,
-    rest::token::{InAppToken, RequestToken, VerifyToken},
+    rest::token::{InAppToken, RequestToken, VerifyToken, VoiceToken},
 };
 
 #[derive(Debug)]
@@ -12,6 +12,7 @@ pub struct Token<'a> {
     pub request_token: RequestToken<'a>,
     pub verify_token: VerifyToken<'a>,
     pub in_app_token: InAppToken<'a>,
+    pub voice_token: VoiceToken<'a>,
 }
 
 impl<'a> Token<'a> {
@@ -19,6 +20,7 @@ impl<'a> Token<'a> {
         let request_token = RequestToken::new(api_key, Rc::clone(&client));
         let verify_token = VerifyToken::new(api_key, Rc::clone(&client));
         let in_app_token = InAppToken::new(api_key, Rc::clone(&client));
+        let voice_token = VoiceToken::new(api_key, Rc::clone(&client));
 
         Token {
             api_key,
@@ -26,6 +28,7 @@ impl<'a> Token<'a> {
             request_token,
             verify_token,
             in_app_token,
+            voice_token,
         }
     }
 }
@@ -0,0 +1,60 @@
+//! Voice tokens are pins read out to the user in a call, for numbers that can't
+//! receive the token by message.
+
+use std::rc::Rc;
+
+use crate::{
+    blocking::http::client,
+    common::{
+        errors,
+        token::voice_token::{VoiceTokenRequest, VoiceTokenResponse},
+    },
+};
+
+#[derive(Debug)]
+pub struct VoiceToken<'a> {
+    api_key: &'a str,
+    client: Rc<client::HttpClient>,
+}
+
+impl<'a> VoiceToken<'a> {
+    pub(crate) fn new(api_key: &'a str, client: Rc<client::HttpClient>) -> VoiceToken<'a> {
+        VoiceToken { api_key, client }
+    }
+
+    /// Call the user and read out a pin, verify it with [`VerifyToken`](super::VerifyToken).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use termii_rust::{blocking::rest::termii, common::token::VoiceTokenRequest};
+    ///
+    /// # fn run() {
+    /// let client = termii::Termii::new("Your API key");
+    ///
+    /// let voice_token_request = VoiceTokenRequest::new("234XXXXXXXXXX".to_string(), 3, 5, 6);
+    ///
+    /// let voice_token_response = client
+    ///     .token
+    ///     .voice_token
+    ///     .send(voice_token_request)
+    ///     .unwrap();
+    ///
+    /// println!("{:?}", voice_token_response.pin_id);
+    /// # }
+    /// ```
+    pub fn send(
+        &self,
+        mut otp_payload: VoiceTokenRequest,
+    ) -> Result<VoiceTokenResponse, errors::HttpError> {
+        otp_payload.set_api_key(self.api_key);
+
+        let response = self
+            .client
+            .post("sms/otp/send/voice", None, None, Some(otp_payload))?;
+
+        let voice_token_response = response_or_error_text_blocking!(response, VoiceTokenResponse);
+
+        Ok(voice_token_response)
+    }
+}
@@ -1,6 +1,13 @@
+use std::io;
+
 use reqwest::Error as ReqwestError;
 use thiserror::Error as ThisError;
 
+use crate::common::{
+    money::{Currency, Decimal, Money},
+    outbox::JobId,
+};
+
 #[derive(ThisError, Debug)]
 pub enum HttpError {
     #[error("Invalid timeout value {0}")]
@@ -31,3 +38,173 @@ pub enum HttpError {
     #[error(transparent)]
     Io(#[from] ReqwestError),
 }
+
+impl HttpError {
+    /// Whether the same request may succeed when sent again later.
+    ///
+    /// Connection failures, rate limiting and server errors are, rejected requests are not.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            HttpError::Io(_) => true,
+            HttpError::JsonError { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Whether termii certainly did not act on the request.
+    ///
+    /// True for requests refused before they were sent and for client errors other than a
+    /// timeout or rate limiting. Connection failures, server errors and unreadable success
+    /// responses leave it open whether the request went through.
+    pub fn is_rejected(&self) -> bool {
+        match self {
+            HttpError::InvalidTimeout(_)
+            | HttpError::MissingQueryParams
+            | HttpError::UrlParseError(_) => true,
+            HttpError::JsonError { status, .. } => {
+                (400..500).contains(status) && *status != 408 && *status != 429
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Why a checked send was refused before or while reaching termii.
+#[derive(ThisError, Debug)]
+pub enum SendError {
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    #[error("The estimated cost {estimated} is more than the balance {balance}.")]
+    InsufficientBalance { estimated: Decimal, balance: Money },
+
+    #[error("The prices are in {prices} but the balance is in {balance}.")]
+    CurrencyMismatch { prices: Currency, balance: Currency },
+
+    #[error("A send with the idempotency key {0:?} is still in flight.")]
+    InFlight(String),
+
+    #[error(transparent)]
+    Http(Box<HttpError>),
+}
+
+impl From<HttpError> for SendError {
+    fn from(err: HttpError) -> SendError {
+        SendError::Http(Box::new(err))
+    }
+}
+
+#[derive(ThisError, Debug)]
+pub enum OtpError {
+    #[error("No active one time token for {0}.")]
+    NotIssued(String),
+
+    #[error("A token was sent recently, retry in {retry_after} seconds.")]
+    CooldownActive { retry_after: u64 },
+
+    #[error("Maximum number of resends ({0}) reached.")]
+    ResendLimitReached(u8),
+
+    #[error("Unsupported pin type {0:?}.")]
+    UnsupportedPinType(String),
+
+    #[error("Otp store error. {0}")]
+    Storage(String),
+
+    #[error(transparent)]
+    Http(Box<HttpError>),
+}
+
+impl From<HttpError> for OtpError {
+    fn from(err: HttpError) -> OtpError {
+        OtpError::Http(Box::new(err))
+    }
+}
+
+#[derive(ThisError, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("Missing required field `{0}`.")]
+    MissingField(&'static str),
+
+    #[error("`{field}` must be between {min} and {max}, got {value}.")]
+    OutOfRange {
+        field: &'static str,
+        min: usize,
+        max: usize,
+        value: usize,
+    },
+
+    #[error("The pin placeholder {placeholder:?} does not appear in the message text.")]
+    PlaceholderNotInMessage { placeholder: String },
+
+    #[error(
+        "`{field}` may only contain letters, digits, spaces, `-`, `.` and `_`, got {value:?}."
+    )]
+    InvalidCharacters { field: &'static str, value: String },
+
+    #[error("The sender ID {0:?} is not approved on your account.")]
+    SenderIdNotApproved(String),
+}
+
+#[derive(ThisError, Debug)]
+pub enum WebhookError {
+    #[error("Unable to read the webhook body. {0}")]
+    Body(String),
+
+    #[error("Unable to parse the webhook payload. {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+
+    #[error("The webhook request has no signature header.")]
+    MissingSignature,
+
+    #[error("The webhook signature does not match the request body.")]
+    InvalidSignature,
+
+    #[error("No WebhookVerifier is configured for the webhook route.")]
+    MissingVerifier,
+}
+
+#[derive(ThisError, Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Placeholder opened at byte {0} is never closed.")]
+    UnclosedPlaceholder(usize),
+
+    #[error("Invalid placeholder name {name:?} at byte {position}.")]
+    InvalidPlaceholder { name: String, position: usize },
+
+    #[error("No value for the template variable `{0}`.")]
+    MissingVariable(String),
+
+    #[error("Unable to render the message for {phone_number}. {source}")]
+    Recipient {
+        phone_number: String,
+        source: Box<TemplateError>,
+    },
+}
+
+#[derive(ThisError, Debug)]
+pub enum OutboxError {
+    #[error("No outbox job with id {0}.")]
+    JobNotFound(JobId),
+
+    #[error("Outbox store error. {0}")]
+    Storage(String),
+}
+
+#[derive(ThisError, Debug)]
+pub enum ExportError {
+    #[error("Unable to write the export. {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Unable to encode a history item. {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Http(Box<HttpError>),
+}
+
+impl From<HttpError> for ExportError {
+    fn from(err: HttpError) -> ExportError {
+        ExportError::Http(Box::new(err))
+    }
+}
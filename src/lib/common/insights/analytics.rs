@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, HashMap};
+
+use futures::{Stream, TryStreamExt};
+
+use crate::common::{
+    insights::history::{DeliveryStatus, HistoryItem},
+    money::Decimal,
+};
+
+/// Totals for a set of sent messages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeliveryStats {
+    pub volume: usize,
+    pub delivered: usize,
+    /// Messages that finished without being delivered, see `failure_reasons`.
+    pub failed: usize,
+    /// Messages termii was still delivering.
+    pub pending: usize,
+    pub reroutes: usize,
+    /// The sum of [`HistoryItem::amount`], in your account currency.
+    pub spend: Decimal,
+    /// Failed messages counted by the status termii gave them.
+    pub failure_reasons: BTreeMap<String, usize>,
+}
+
+impl DeliveryStats {
+    /// The share of finished messages that were delivered, `None` until one finishes.
+    pub fn delivery_rate(&self) -> Option<f64> {
+        let finished = self.delivered + self.failed;
+
+        if finished == 0 {
+            return None;
+        }
+
+        Some(self.delivered as f64 / finished as f64)
+    }
+
+    fn record(&mut self, item: &HistoryItem) {
+        self.volume += 1;
+        self.reroutes += item.reroute;
+        self.spend += item.amount.amount;
+
+        match item.delivery_status() {
+            DeliveryStatus::Delivered => self.delivered += 1,
+            status if status.is_terminal() => {
+                self.failed += 1;
+                *self.failure_reasons.entry(item.status.clone()).or_default() += 1;
+            }
+            _ => self.pending += 1,
+        }
+    }
+}
+
+/// Delivery statistics over your message history, in total and grouped by sender, channel
+/// (`sms_type`), network and day.
+///
+/// History items carry no network, give the networks of your receivers with
+/// [`HistoryAnalytics::with_networks`], from a number search or status lookup. Receivers without
+/// one are grouped under `"unknown"`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::insights::{HistoryAnalytics, HistoryItem};
+///
+/// let history: Vec<HistoryItem> = serde_json::from_str(
+///     r#"[{
+///     "sender": "N-Alert", "receiver": "2347062668208", "message": "Your pin is 123456",
+///     "amount": "0.70", "reroute": 1, "status": "DELIVERED", "sms_type": "plain",
+///     "send_by": "sender", "media_url": null, "message_id": "5508751839629937023",
+///     "notify_url": null, "notify_id": null, "created_at": "2024-01-31 12:26:36"
+/// }, {
+///     "sender": "N-Alert", "receiver": "2348031234567", "message": "Your pin is 654321",
+///     "amount": "0.70", "reroute": 0, "status": "DND Active on Phone Number", "sms_type": "plain",
+///     "send_by": "sender", "media_url": null, "message_id": "5508751839629937024",
+///     "notify_url": null, "notify_id": null, "created_at": "2024-01-31 12:27:01"
+/// }]"#,
+/// )
+/// .unwrap();
+///
+/// let mut analytics =
+///     HistoryAnalytics::new().with_networks([("2347062668208".to_string(), "MTN".to_string())]);
+/// analytics.extend(&history);
+///
+/// assert_eq!(analytics.total.volume, 2);
+/// assert_eq!(analytics.total.delivery_rate(), Some(0.5));
+/// assert_eq!(analytics.total.spend.to_string(), "1.40");
+/// assert_eq!(analytics.total.failure_reasons["DND Active on Phone Number"], 1);
+/// assert_eq!(analytics.by_network["MTN"].delivered, 1);
+/// assert_eq!(analytics.by_network["unknown"].failed, 1);
+/// assert_eq!(analytics.by_day["2024-01-31"].reroutes, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HistoryAnalytics {
+    pub total: DeliveryStats,
+    pub by_sender: BTreeMap<String, DeliveryStats>,
+    pub by_channel: BTreeMap<String, DeliveryStats>,
+    pub by_network: BTreeMap<String, DeliveryStats>,
+    /// Keyed by the `YYYY-MM-DD` of `created_at`.
+    pub by_day: BTreeMap<String, DeliveryStats>,
+    networks: HashMap<String, String>,
+}
+
+impl HistoryAnalytics {
+    pub const UNKNOWN_NETWORK: &'static str = "unknown";
+
+    pub fn new() -> HistoryAnalytics {
+        HistoryAnalytics::default()
+    }
+
+    /// Group receivers by network, from `(phone number, network)` pairs.
+    pub fn with_networks<I>(mut self, networks: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        self.networks.extend(
+            networks
+                .into_iter()
+                .map(|(number, network)| (normalize_number(&number).to_string(), network)),
+        );
+        self
+    }
+
+    pub fn record(&mut self, item: &HistoryItem) {
+        let network = self
+            .networks
+            .get(normalize_number(&item.receiver))
+            .map(String::as_str)
+            .unwrap_or(Self::UNKNOWN_NETWORK);
+        let day = item.created_at.get(..10).unwrap_or(&item.created_at);
+
+        self.total.record(item);
+
+        for (groups, key) in [
+            (&mut self.by_sender, item.sender.as_str()),
+            (&mut self.by_channel, item.sms_type.as_str()),
+            (&mut self.by_network, network),
+            (&mut self.by_day, day),
+        ] {
+            groups.entry(key.to_string()).or_default().record(item);
+        }
+    }
+
+    /// Record every item of a fallible iterator, such as the blocking `History::query_iter`,
+    /// stopping at the first error.
+    pub fn record_all<I, E>(mut self, items: I) -> Result<Self, E>
+    where
+        I: IntoIterator<Item = Result<HistoryItem, E>>,
+    {
+        for item in items {
+            self.record(&item?);
+        }
+
+        Ok(self)
+    }
+
+    /// Record every item of a fallible stream, such as the async `History::query_stream`,
+    /// stopping at the first error.
+    pub async fn record_stream<S, E>(self, items: S) -> Result<Self, E>
+    where
+        S: Stream<Item = Result<HistoryItem, E>>,
+    {
+        items
+            .try_fold(self, |mut analytics, item| async move {
+                analytics.record(&item);
+                Ok(analytics)
+            })
+            .await
+    }
+}
+
+impl Extend<HistoryItem> for HistoryAnalytics {
+    fn extend<I: IntoIterator<Item = HistoryItem>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.record(&item));
+    }
+}
+
+impl<'a> Extend<&'a HistoryItem> for HistoryAnalytics {
+    fn extend<I: IntoIterator<Item = &'a HistoryItem>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.record(item));
+    }
+}
+
+impl FromIterator<HistoryItem> for HistoryAnalytics {
+    fn from_iter<I: IntoIterator<Item = HistoryItem>>(items: I) -> HistoryAnalytics {
+        let mut analytics = HistoryAnalytics::new();
+        analytics.extend(items);
+        analytics
+    }
+}
+
+fn normalize_number(number: &str) -> &str {
+    number.trim().trim_start_matches('+')
+}
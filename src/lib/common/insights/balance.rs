@@ -1,8 +1,233 @@
+use std::{
+    fmt,
+    sync::{mpsc::Sender, Mutex},
+};
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+use crate::common::{
+    money::{Currency, Decimal, Money},
+    switch::{
+        messaging::{MessageBulkResponse, MessageResponse},
+        number::NumberMessageResponse,
+    },
+};
+
+/// Your account balance.
+///
+/// `balance` carries the currency of the response.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{insights::BalanceItem, money::Decimal};
+///
+/// let item: BalanceItem =
+///     serde_json::from_str(r#"{"user": "Your org", "balance": "0.70", "currency": "NGN"}"#).unwrap();
+///
+/// assert_eq!(item.balance.amount, Decimal::new(70, 2));
+/// assert_eq!(item.balance.to_string(), "NGN 0.70");
+/// assert_eq!(serde_json::to_value(&item).unwrap()["currency"], "NGN");
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "RawBalanceItem", into = "RawBalanceItem")]
 pub struct BalanceItem {
     pub user: String,
-    pub currency: String,
-    pub balance: f64,
+    pub balance: Money,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawBalanceItem {
+    user: String,
+    currency: String,
+    balance: Money,
+}
+
+impl From<RawBalanceItem> for BalanceItem {
+    fn from(raw: RawBalanceItem) -> BalanceItem {
+        BalanceItem {
+            balance: raw.balance.with_currency(Currency::new(&raw.currency)),
+            user: raw.user,
+        }
+    }
+}
+
+impl From<BalanceItem> for RawBalanceItem {
+    fn from(item: BalanceItem) -> RawBalanceItem {
+        RawBalanceItem {
+            user: item.user,
+            currency: item
+                .balance
+                .currency
+                .as_ref()
+                .map(|currency| currency.to_string())
+                .unwrap_or_default(),
+            balance: item.balance,
+        }
+    }
+}
+
+/// Responses that report the account balance left after the request.
+pub trait ReportsBalance {
+    fn reported_balance(&self) -> Money;
+}
+
+impl ReportsBalance for BalanceItem {
+    fn reported_balance(&self) -> Money {
+        self.balance.clone()
+    }
+}
+
+impl ReportsBalance for MessageResponse {
+    fn reported_balance(&self) -> Money {
+        self.balance.clone()
+    }
+}
+
+impl ReportsBalance for MessageBulkResponse {
+    fn reported_balance(&self) -> Money {
+        self.balance.clone()
+    }
+}
+
+impl ReportsBalance for NumberMessageResponse {
+    fn reported_balance(&self) -> Money {
+        self.balance.clone()
+    }
+}
+
+/// Sent when the balance drops below one of the watched thresholds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceAlert {
+    pub threshold: Decimal,
+    pub balance: Money,
+}
+
+/// Keeps the latest known balance and raises a [`BalanceAlert`] when it drops below a threshold.
+///
+/// Each threshold fires once when crossed and again only after the balance has gone back above it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::sync::mpsc;
+///
+/// use termii_rust::common::{
+///     insights::BalanceWatcher,
+///     money::{Currency, Decimal, Money},
+/// };
+///
+/// let (sender, alerts) = mpsc::channel();
+/// let watcher = BalanceWatcher::with_sender([Decimal::from(1000), Decimal::from(200)], sender);
+///
+/// watcher.record(Money::new(Decimal::from(850), Some(Currency::new("NGN"))));
+///
+/// assert_eq!(watcher.latest().unwrap().to_string(), "NGN 850");
+/// assert_eq!(alerts.try_recv().unwrap().threshold, Decimal::from(1000));
+/// assert!(alerts.try_recv().is_err());
+/// ```
+pub struct BalanceWatcher {
+    thresholds: Vec<Decimal>,
+    on_alert: Box<dyn Fn(BalanceAlert) + Send + Sync>,
+    state: Mutex<WatcherState>,
+}
+
+#[derive(Debug, Default)]
+struct WatcherState {
+    balance: Option<Money>,
+    currency: Option<Currency>,
+    below: Vec<bool>,
+}
+
+impl BalanceWatcher {
+    pub fn new<T, F>(thresholds: T, on_alert: F) -> BalanceWatcher
+    where
+        T: IntoIterator<Item = Decimal>,
+        F: Fn(BalanceAlert) + Send + Sync + 'static,
+    {
+        let mut thresholds: Vec<Decimal> = thresholds.into_iter().collect();
+        thresholds.sort_by(|a, b| b.cmp(a));
+
+        BalanceWatcher {
+            state: Mutex::new(WatcherState {
+                below: vec![false; thresholds.len()],
+                ..WatcherState::default()
+            }),
+            thresholds,
+            on_alert: Box::new(on_alert),
+        }
+    }
+
+    /// Send alerts down a channel instead of calling a closure.
+    pub fn with_sender<T>(thresholds: T, sender: Sender<BalanceAlert>) -> BalanceWatcher
+    where
+        T: IntoIterator<Item = Decimal>,
+    {
+        BalanceWatcher::new(thresholds, move |alert| {
+            let _ = sender.send(alert);
+        })
+    }
+
+    /// The latest known balance.
+    pub fn latest(&self) -> Option<Money> {
+        self.lock().balance.clone()
+    }
+
+    /// The currency of the account, known once a response carrying it has been recorded.
+    pub fn currency(&self) -> Option<Currency> {
+        self.lock().currency.clone()
+    }
+
+    /// Record the balance reported by a balance lookup or a send response.
+    pub fn observe<R: ReportsBalance + ?Sized>(&self, response: &R) {
+        self.record(response.reported_balance());
+    }
+
+    /// Record a balance, send responses carry no currency so the last known one is kept.
+    pub fn record(&self, mut balance: Money) {
+        let mut alerts = Vec::new();
+
+        {
+            let mut state = self.lock();
+
+            match &balance.currency {
+                Some(currency) => state.currency = Some(currency.clone()),
+                None => balance.currency = state.currency.clone(),
+            }
+
+            for (index, threshold) in self.thresholds.iter().enumerate() {
+                let below = balance.amount < *threshold;
+
+                if below && !state.below[index] {
+                    alerts.push(BalanceAlert {
+                        threshold: *threshold,
+                        balance: balance.clone(),
+                    });
+                }
+
+                state.below[index] = below;
+            }
+
+            state.balance = Some(balance);
+        }
+
+        for alert in alerts {
+            (self.on_alert)(alert);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, WatcherState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for BalanceWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BalanceWatcher")
+            .field("thresholds", &self.thresholds)
+            .field("state", &self.state)
+            .finish()
+    }
 }
@@ -0,0 +1,91 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::common::errors::HttpError;
+
+/// The outcome of one lookup in a bulk search or status request.
+#[derive(Debug)]
+pub struct LookupResult<T> {
+    pub phone_number: String,
+    pub result: Result<T, HttpError>,
+}
+
+/// A cache for number lookups so repeat screening doesn't cost money.
+///
+/// Implement this to share lookups across processes, failed lookups are never cached.
+///
+/// The async clients call `get` and `put` from inside their futures, so implementations must
+/// answer without blocking, such as from a local map kept in sync in the background. A cache
+/// behind a network round trip should be wrapped in one that only reads from memory.
+pub trait LookupCache<T> {
+    fn get(&self, key: &str) -> Option<T>;
+
+    fn put(&self, key: &str, value: T);
+}
+
+/// A [`LookupCache`] that keeps lookups in process memory for a fixed time.
+#[derive(Debug)]
+pub struct InMemoryLookupCache<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, T)>>,
+}
+
+impl<T: Clone> InMemoryLookupCache<T> {
+    pub fn new(ttl: Duration) -> InMemoryLookupCache<T> {
+        InMemoryLookupCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Drop the lookups older than the ttl.
+    pub fn purge_expired(&self) {
+        let ttl = self.ttl;
+        self.lock()
+            .retain(|_, (stored_at, _)| stored_at.elapsed() < ttl);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, T)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T: Clone> LookupCache<T> for InMemoryLookupCache<T> {
+    fn get(&self, key: &str) -> Option<T> {
+        let mut entries = self.lock();
+
+        match entries.get(key) {
+            Some((stored_at, value)) if stored_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn put(&self, key: &str, value: T) {
+        self.lock().insert(key.to_string(), (Instant::now(), value));
+    }
+}
+
+/// A [`LookupCache`] that never remembers anything.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoLookupCache;
+
+impl<T> LookupCache<T> for NoLookupCache {
+    fn get(&self, _key: &str) -> Option<T> {
+        None
+    }
+
+    fn put(&self, _key: &str, _value: T) {}
+}
@@ -0,0 +1,231 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    errors::ExportError,
+    insights::{history::HistoryItem, query::HistoryQuery},
+    money::Currency,
+};
+
+/// The CSV columns, in the order they are written. New columns are only ever added at the end.
+pub const HISTORY_CSV_COLUMNS: [&str; 14] = [
+    "message_id",
+    "created_at",
+    "sender",
+    "receiver",
+    "message",
+    "amount",
+    "currency",
+    "reroute",
+    "status",
+    "sms_type",
+    "send_by",
+    "media_url",
+    "notify_url",
+    "notify_id",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON encoded [`HistoryItem`] per line.
+    JsonLines,
+}
+
+/// The newest message of an export, keep it to export only what was sent since.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExportCursor {
+    pub message_id: String,
+    pub created_at: String,
+}
+
+impl From<&HistoryItem> for ExportCursor {
+    fn from(item: &HistoryItem) -> ExportCursor {
+        ExportCursor {
+            message_id: item.message_id.clone(),
+            created_at: item.created_at.clone(),
+        }
+    }
+}
+
+/// What an export wrote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub exported: usize,
+    /// The newest message written, or the cursor the export resumed after when nothing new was
+    /// found.
+    pub cursor: Option<ExportCursor>,
+}
+
+/// Writes your message history as CSV or JSON Lines.
+///
+/// Messages are written as termii pages them, newest first, one page at a time. Pass the
+/// [`ExportSummary::cursor`] of the previous export to [`HistoryExporter::resume_after`] to only
+/// write the messages sent since. A failed export returns no cursor, run it again from the
+/// previous one.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{
+///     insights::{ExportFormat, HistoryExporter, HistoryItem},
+///     money::Currency,
+/// };
+///
+/// let item: HistoryItem = serde_json::from_str(
+///     r#"{
+///     "sender": "N-Alert", "receiver": "2347062668208", "message": "Hello, world",
+///     "amount": "0.70", "reroute": 0, "status": "DELIVERED", "sms_type": "plain",
+///     "send_by": "sender", "media_url": null, "message_id": "5508751839629937023",
+///     "notify_url": null, "notify_id": null, "created_at": "2024-01-31 12:26:36"
+/// }"#,
+/// )
+/// .unwrap();
+///
+/// let exporter = HistoryExporter::new(ExportFormat::Csv).currency(Currency::new("NGN"));
+///
+/// let mut csv = Vec::new();
+/// exporter.write_header(&mut csv).unwrap();
+/// exporter.write_item(&item, &mut csv).unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(csv).unwrap().lines().nth(1),
+///     Some(
+///         "5508751839629937023,2024-01-31 12:26:36,N-Alert,2347062668208,\"Hello, world\",0.70,NGN,0,\
+///         DELIVERED,plain,sender,,,"
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct HistoryExporter {
+    format: ExportFormat,
+    query: HistoryQuery,
+    after: Option<ExportCursor>,
+    header: bool,
+    currency: Option<Currency>,
+}
+
+impl HistoryExporter {
+    pub fn new(format: ExportFormat) -> HistoryExporter {
+        HistoryExporter {
+            format,
+            query: HistoryQuery::default(),
+            after: None,
+            header: true,
+            currency: None,
+        }
+    }
+
+    /// Only export the messages matching `query`, such as a month with `since` and `until`.
+    pub fn query(mut self, query: HistoryQuery) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Only export the messages newer than `cursor`.
+    pub fn resume_after(mut self, cursor: ExportCursor) -> Self {
+        self.after = Some(cursor);
+        self
+    }
+
+    /// Whether to start a CSV export with the column names, turn it off when appending to an
+    /// earlier export.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// The currency written in the `currency` column.
+    ///
+    /// The history doesn't name the currency of its amounts, pass the one of your
+    /// [`BalanceItem`](crate::common::insights::BalanceItem) to fill the column, it is left
+    /// empty otherwise.
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    pub(crate) fn history_query(&self) -> &HistoryQuery {
+        &self.query
+    }
+
+    pub(crate) fn resumed_from(&self) -> Option<&ExportCursor> {
+        self.after.as_ref()
+    }
+
+    /// Whether `item` was already written by the export this one resumes after, and so is every
+    /// older message.
+    pub(crate) fn is_exported(&self, item: &HistoryItem) -> bool {
+        self.after.as_ref().is_some_and(|after| {
+            item.message_id == after.message_id || item.created_at < after.created_at
+        })
+    }
+
+    /// Write the CSV column names, if the format and settings call for them.
+    pub fn write_header<W: io::Write>(&self, writer: &mut W) -> Result<(), ExportError> {
+        if self.format == ExportFormat::Csv && self.header {
+            writeln!(writer, "{}", HISTORY_CSV_COLUMNS.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_item<W: io::Write>(
+        &self,
+        item: &HistoryItem,
+        writer: &mut W,
+    ) -> Result<(), ExportError> {
+        match self.format {
+            ExportFormat::Csv => {
+                let amount = item.amount.amount.to_string();
+                let currency = item
+                    .amount
+                    .currency
+                    .as_ref()
+                    .or(self.currency.as_ref())
+                    .map(|c| c.code());
+                let reroute = item.reroute.to_string();
+
+                let row = [
+                    item.message_id.as_str(),
+                    &item.created_at,
+                    &item.sender,
+                    &item.receiver,
+                    &item.message,
+                    &amount,
+                    currency.unwrap_or_default(),
+                    &reroute,
+                    &item.status,
+                    &item.sms_type,
+                    &item.send_by,
+                    item.media_url.as_deref().unwrap_or_default(),
+                    item.notify_url.as_deref().unwrap_or_default(),
+                    item.notify_id.as_deref().unwrap_or_default(),
+                ];
+
+                let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+                writeln!(writer, "{}", row.join(","))?;
+            }
+            ExportFormat::JsonLines => {
+                serde_json::to_writer(&mut *writer, item)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Quote a field when it holds a separator, a quote or a line break, doubling inner quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -1,22 +1,119 @@
 use serde::{Deserialize, Serialize};
 
+use crate::common::{lenient, money::Money};
+
+/// A message from your sending history.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{insights::HistoryItem, money::Decimal};
+///
+/// let item: HistoryItem = serde_json::from_str(
+///     r#"{
+///     "sender": "N-Alert",
+///     "receiver": 233200000000,
+///     "message": "You have attempted to sign in, your OTP is 123456",
+///     "amount": 1,
+///     "reroute": "0",
+///     "status": "DELIVERED",
+///     "sms_type": "plain",
+///     "send_by": "sender",
+///     "media_url": null,
+///     "message_id": "5508751839629937023",
+///     "notify_url": "",
+///     "notify_id": "",
+///     "created_at": "2020-07-21 12:26:36"
+/// }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(item.receiver, "233200000000");
+/// assert_eq!(item.amount.amount, Decimal::ONE);
+/// assert_eq!(item.notify_url, None);
+/// ```
 #[derive(Debug, Deserialize, Serialize)]
 pub struct HistoryItem {
     pub sender: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub receiver: String,
     pub message: String,
-    pub amount: usize,
+    /// The cost of the message in your account currency, which the history doesn't name so
+    /// `currency` is `None`.
+    pub amount: Money,
+    #[serde(deserialize_with = "lenient::integer_or_string")]
     pub reroute: usize,
     pub status: String,
     pub sms_type: String,
     pub send_by: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub media_url: Option<String>,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub message_id: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub notify_url: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub notify_id: Option<String>,
     pub created_at: String,
 }
 
+impl HistoryItem {
+    pub fn delivery_status(&self) -> DeliveryStatus {
+        DeliveryStatus::from(self.status.as_str())
+    }
+}
+
+/// The delivery state of a sent message, parsed from [`HistoryItem::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeliveryStatus {
+    Pending,
+    Sent,
+    Delivered,
+    Failed,
+    Rejected,
+    DndBlocked,
+    Expired,
+    Unknown(String),
+}
+
+impl DeliveryStatus {
+    /// Whether the status can no longer change.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(
+            self,
+            DeliveryStatus::Pending | DeliveryStatus::Sent | DeliveryStatus::Unknown(_)
+        )
+    }
+}
+
+impl From<&str> for DeliveryStatus {
+    fn from(status: &str) -> DeliveryStatus {
+        let lowercase = status.to_lowercase();
+
+        if lowercase.contains("dnd") {
+            DeliveryStatus::DndBlocked
+        } else if lowercase.contains("undeliver") || lowercase.contains("fail") {
+            DeliveryStatus::Failed
+        } else if lowercase.contains("deliver") {
+            DeliveryStatus::Delivered
+        } else if lowercase.contains("reject") {
+            DeliveryStatus::Rejected
+        } else if lowercase.contains("expire") {
+            DeliveryStatus::Expired
+        } else if lowercase.contains("sent") || lowercase.contains("submit") {
+            DeliveryStatus::Sent
+        } else if lowercase.contains("pending")
+            || lowercase.contains("queue")
+            || lowercase.contains("process")
+        {
+            DeliveryStatus::Pending
+        } else {
+            DeliveryStatus::Unknown(status.to_string())
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct HistoryResponse {
     pub(crate) data: HistoryResponseData,
@@ -1,9 +1,21 @@
+pub mod analytics;
+pub use analytics::*;
+
 pub mod balance;
 pub use balance::*;
 
+pub mod cache;
+pub use cache::*;
+
+pub mod export;
+pub use export::*;
+
 pub mod history;
 pub use history::*;
 
+pub mod query;
+pub use query::*;
+
 pub mod search;
 pub use search::*;
 
@@ -0,0 +1,162 @@
+use crate::common::{
+    insights::history::{DeliveryStatus, HistoryItem},
+    switch::messaging::Channel,
+};
+
+/// Filters for your message history.
+///
+/// Termii's inbox takes no filters, only a page number, so none are sent and every filter is
+/// applied here to each page as it arrives. A query pages through your whole history from the
+/// newest message, stopping early only at the first message older than `since` or once `limit`
+/// matches are found, so a narrow query over a long history still costs a request per page.
+///
+/// Dates are compared as text against `created_at` (`YYYY-MM-DD HH:MM:SS`) at the precision
+/// given, `until("2024-01-31")` includes the whole day.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::insights::{DeliveryStatus, HistoryItem, HistoryQuery};
+///
+/// let query = HistoryQuery::new()
+///     .receiver("+2347062668208")
+///     .status(DeliveryStatus::Delivered)
+///     .since("2024-01-01")
+///     .until("2024-01-31");
+///
+/// let item: HistoryItem = serde_json::from_str(
+///     r#"{
+///     "sender": "N-Alert", "receiver": "2347062668208", "message": "Your pin is 123456",
+///     "amount": 1, "reroute": 0, "status": "DELIVERED", "sms_type": "plain", "send_by": "sender",
+///     "media_url": null, "message_id": "5508751839629937023", "notify_url": null,
+///     "notify_id": null, "created_at": "2024-01-31 23:59:59"
+/// }"#,
+/// )
+/// .unwrap();
+///
+/// assert!(query.matches(&item));
+/// assert!(!query.is_before_range(&item));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    receiver: Option<String>,
+    sender: Option<String>,
+    status: Option<DeliveryStatus>,
+    since: Option<String>,
+    until: Option<String>,
+    sms_type: Option<String>,
+    channel: Option<Channel>,
+    message_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl HistoryQuery {
+    pub fn new() -> HistoryQuery {
+        HistoryQuery::default()
+    }
+
+    pub fn receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.receiver = Some(receiver.into());
+        self
+    }
+
+    /// The sender ID the message was sent from.
+    pub fn sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    pub fn status(mut self, status: DeliveryStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Messages created at or after `since`.
+    pub fn since(mut self, since: impl Into<String>) -> Self {
+        self.since = Some(since.into());
+        self
+    }
+
+    /// Messages created at or before `until`.
+    pub fn until(mut self, until: impl Into<String>) -> Self {
+        self.until = Some(until.into());
+        self
+    }
+
+    pub fn sms_type(mut self, sms_type: impl Into<String>) -> Self {
+        self.sms_type = Some(sms_type.into());
+        self
+    }
+
+    /// Messages sent on `channel`, termii reports it in `send_by` or `sms_type` depending on the
+    /// route.
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    pub fn message_id(mut self, message_id: impl Into<String>) -> Self {
+        self.message_id = Some(message_id.into());
+        self
+    }
+
+    /// Stop after this many matching messages.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn matches(&self, item: &HistoryItem) -> bool {
+        let same_number = |a: &str, b: &str| a.trim_start_matches('+') == b.trim_start_matches('+');
+
+        passes(self.receiver.as_deref(), |receiver| {
+            same_number(receiver, &item.receiver)
+        }) && passes(self.sender.as_deref(), |sender| {
+            sender.eq_ignore_ascii_case(&item.sender)
+        }) && passes(self.status.as_ref(), |status| {
+            *status == item.delivery_status()
+        }) && passes(self.since.as_deref(), |since| {
+            date_prefix(&item.created_at, since) >= since
+        }) && passes(self.until.as_deref(), |until| {
+            date_prefix(&item.created_at, until) <= until
+        }) && passes(self.sms_type.as_deref(), |sms_type| {
+            sms_type.eq_ignore_ascii_case(&item.sms_type)
+        }) && passes(self.channel.as_ref(), |channel| {
+            channel.as_str().eq_ignore_ascii_case(&item.send_by)
+                || channel.as_str().eq_ignore_ascii_case(&item.sms_type)
+        }) && passes(self.message_id.as_deref(), |message_id| {
+            message_id == item.message_id
+        })
+    }
+
+    /// How many matches to stop after, message ids are unique so a lookup by id stops at the first.
+    pub(crate) fn max_matches(&self) -> usize {
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        if self.message_id.is_some() {
+            limit.min(1)
+        } else {
+            limit
+        }
+    }
+
+    /// Whether `item` is older than `since`, and so is everything after it in the history.
+    pub fn is_before_range(&self, item: &HistoryItem) -> bool {
+        self.since
+            .as_deref()
+            .is_some_and(|since| date_prefix(&item.created_at, since) < since)
+    }
+}
+
+/// Whether an item passes a filter, an unset filter lets everything through.
+fn passes<T: ?Sized>(filter: Option<&T>, check: impl FnOnce(&T) -> bool) -> bool {
+    match filter {
+        Some(value) => check(value),
+        None => true,
+    }
+}
+
+/// `created_at` cut to the precision of `bound`.
+fn date_prefix<'c>(created_at: &'c str, bound: &str) -> &'c str {
+    created_at.get(..bound.len()).unwrap_or(created_at)
+}
@@ -1,11 +1,37 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+use crate::common::lenient;
+
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::insights::SearchItem;
+///
+/// let item: SearchItem = serde_json::from_str(
+///     r#"{
+///     "number": 2347089239023,
+///     "status": "DND blacklisted",
+///     "network": "Airtel Nigeria",
+///     "network_code": 62120,
+///     "dnd_active": 1,
+///     "message": "Number is DND blacklisted"
+/// }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(item.number, "2347089239023");
+/// assert_eq!(item.network_code, "62120");
+/// assert!(item.dnd_active);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SearchItem {
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub number: String,
     pub message: String,
     pub status: String,
+    #[serde(deserialize_with = "lenient::bool_or_int")]
     pub dnd_active: bool,
     pub network: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub network_code: String,
 }
@@ -1,48 +1,206 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+use crate::common::lenient;
+
+/// The result of a number status lookup.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::insights::status::{LineType, StatusItem};
+///
+/// let status: StatusItem = serde_json::from_str(
+///     r#"{
+///     "result": [{
+///         "routeDetail": {"number": "2348753243651", "ported": 1},
+///         "countryDetail": {"countryCode": "234", "mobileCountryCode": "621", "iso": "NG"},
+///         "operatorDetail": {
+///             "operatorCode": "ANG",
+///             "operatorName": "Airtel Nigeria",
+///             "mobileNumberCode": "802",
+///             "mobileRoutingCode": "",
+///             "carrierIdentificationCode": "",
+///             "lineType": "Mobile"
+///         },
+///         "status": 200
+///     }]
+/// }"#,
+/// )
+/// .unwrap();
+///
+/// assert!(status.is_ported());
+/// assert_eq!(status.operator().unwrap().operator_name, "Airtel Nigeria");
+/// assert_eq!(status.line_type(), Some(&LineType::Mobile));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct StatusItem {
-    result: Vec<ResultResponse>,
+    pub result: Vec<ResultResponse>,
+}
+
+impl StatusItem {
+    /// Whether any result reports the number as ported to another operator.
+    pub fn is_ported(&self) -> bool {
+        self.result.iter().any(ResultResponse::is_ported)
+    }
+
+    /// The operator of the first result.
+    pub fn operator(&self) -> Option<&OperatorDetail> {
+        self.result.first().map(|result| &result.operator_detail)
+    }
+
+    /// The line type of the first result.
+    pub fn line_type(&self) -> Option<&LineType> {
+        self.operator().map(|operator| &operator.line_type)
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ResultResponse {
     #[serde(rename = "routeDetail")]
-    route_detail: RouteDetail,
+    pub route_detail: RouteDetail,
     #[serde(rename = "countryDetail")]
-    country_detail: CountryDetail,
+    pub country_detail: CountryDetail,
     #[serde(rename = "operatorDetail")]
-    operator_detail: OperatorDetail,
-    status: i64,
+    pub operator_detail: OperatorDetail,
+    pub status: LookupStatus,
+}
+
+impl ResultResponse {
+    pub fn is_ported(&self) -> bool {
+        self.route_detail.ported
+    }
+
+    pub fn operator(&self) -> &OperatorDetail {
+        &self.operator_detail
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CountryDetail {
-    #[serde(rename = "countryCode")]
-    country_code: String,
-    #[serde(rename = "mobileCountryCode")]
-    mobile_country_code: String,
-    iso: String,
+    #[serde(rename = "countryCode", deserialize_with = "lenient::string_or_number")]
+    pub country_code: String,
+    #[serde(
+        rename = "mobileCountryCode",
+        deserialize_with = "lenient::string_or_number"
+    )]
+    pub mobile_country_code: String,
+    pub iso: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OperatorDetail {
     #[serde(rename = "operatorCode")]
-    operator_code: String,
+    pub operator_code: String,
     #[serde(rename = "operatorName")]
-    operator_name: String,
-    #[serde(rename = "mobileNumberCode")]
-    mobile_number_code: String,
+    pub operator_name: String,
+    #[serde(
+        rename = "mobileNumberCode",
+        deserialize_with = "lenient::string_or_number"
+    )]
+    pub mobile_number_code: String,
     #[serde(rename = "mobileRoutingCode")]
-    mobile_routing_code: String,
+    pub mobile_routing_code: String,
     #[serde(rename = "carrierIdentificationCode")]
-    carrier_identification_code: String,
+    pub carrier_identification_code: String,
     #[serde(rename = "lineType")]
-    line_type: String,
+    pub line_type: LineType,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RouteDetail {
-    number: String,
-    ported: i64,
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub number: String,
+    #[serde(
+        deserialize_with = "lenient::bool_or_int",
+        serialize_with = "lenient::bool_to_int"
+    )]
+    pub ported: bool,
+}
+
+/// The kind of line a number belongs to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum LineType {
+    Mobile,
+    FixedLine,
+    FixedLineOrMobile,
+    Voip,
+    TollFree,
+    PremiumRate,
+    SharedCost,
+    Pager,
+    Unknown(String),
+}
+
+impl LineType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LineType::Mobile => "Mobile",
+            LineType::FixedLine => "Fixed Line",
+            LineType::FixedLineOrMobile => "Fixed Line or Mobile",
+            LineType::Voip => "VoIP",
+            LineType::TollFree => "Toll Free",
+            LineType::PremiumRate => "Premium Rate",
+            LineType::SharedCost => "Shared Cost",
+            LineType::Pager => "Pager",
+            LineType::Unknown(line_type) => line_type,
+        }
+    }
+}
+
+impl From<String> for LineType {
+    fn from(line_type: String) -> LineType {
+        let normalized: String = line_type
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+
+        match normalized.as_str() {
+            "mobile" | "cellular" => LineType::Mobile,
+            "fixed" | "fixedline" | "landline" => LineType::FixedLine,
+            "fixedlineormobile" => LineType::FixedLineOrMobile,
+            "voip" => LineType::Voip,
+            "tollfree" => LineType::TollFree,
+            "premiumrate" => LineType::PremiumRate,
+            "sharedcost" => LineType::SharedCost,
+            "pager" => LineType::Pager,
+            _ => LineType::Unknown(line_type),
+        }
+    }
+}
+
+impl From<LineType> for String {
+    fn from(line_type: LineType) -> String {
+        line_type.as_str().to_string()
+    }
+}
+
+/// Whether termii could resolve the number, `200` in the response means it could.
+///
+/// Older responses report a resolved number as `0`, which is read as a success too.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "i64", into = "i64")]
+pub enum LookupStatus {
+    Success,
+    Failed(i64),
+}
+
+impl From<i64> for LookupStatus {
+    fn from(status: i64) -> LookupStatus {
+        match status {
+            200 | 0 => LookupStatus::Success,
+            code => LookupStatus::Failed(code),
+        }
+    }
+}
+
+impl From<LookupStatus> for i64 {
+    fn from(status: LookupStatus) -> i64 {
+        match status {
+            LookupStatus::Success => 200,
+            LookupStatus::Failed(code) => code,
+        }
+    }
 }
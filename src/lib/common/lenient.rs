@@ -0,0 +1,223 @@
+//! Tolerant deserializers for the fields termii encodes inconsistently.
+//!
+//! Ids and amounts come back as strings from some endpoints and numbers from others, and optional
+//! values are sent as `""` as often as `null`. Use these with `#[serde(deserialize_with = "...")]`,
+//! adding `default` on the `Option` ones so missing fields are accepted too.
+
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use rust_decimal::Decimal;
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serializer,
+};
+
+/// A string, or an integer turned into its string form.
+pub(crate) fn string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(StringOrNumber)
+}
+
+/// Like [`string_or_number`], with `null` and empty strings as `None`.
+pub(crate) fn optional_string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        Option::<Lenient<String, StringOrNumber>>::deserialize(deserializer)?
+            .map(|value| value.0)
+            .filter(|value| !value.trim().is_empty()),
+    )
+}
+
+/// A string where `null` and empty strings are `None`.
+pub(crate) fn empty_string_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.filter(|value| !value.trim().is_empty()))
+}
+
+/// A decimal from a number or a numeric string, thousands separators allowed.
+pub(crate) fn decimal_or_string<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(DecimalOrString)
+}
+
+/// Like [`decimal_or_string`], with `null` and empty strings as `None`.
+pub(crate) fn optional_decimal_or_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<Decimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    optional_string_or_number(deserializer)?
+        .map(|value| DecimalOrString.visit_str(&value))
+        .transpose()
+}
+
+/// An integer from a number or a numeric string.
+pub(crate) fn integer_or_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + TryFrom<i64> + TryFrom<u64>,
+{
+    deserializer.deserialize_any(IntegerOrString(PhantomData))
+}
+
+/// A bool from `true`/`false`, `0`/`1` or their string forms.
+pub(crate) fn bool_or_int<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(BoolOrInt)
+}
+
+pub(crate) fn bool_to_int<S>(value: &bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_i64(*value as i64)
+}
+
+/// Deserializes a `T` through the visitor `V`, so the helpers above compose with `Option`.
+struct Lenient<T, V>(T, PhantomData<V>);
+
+impl<'de, T, V> Deserialize<'de> for Lenient<T, V>
+where
+    V: Visitor<'de, Value = T> + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Lenient(
+            deserializer.deserialize_any(V::default())?,
+            PhantomData,
+        ))
+    }
+}
+
+#[derive(Default)]
+struct StringOrNumber;
+
+impl<'de> Visitor<'de> for StringOrNumber {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string or an integer")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<String, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<String, E> {
+        Ok(value.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<String, E> {
+        // Integers wider than 64 bits arrive as floats with their low digits already rounded
+        // away, a wrong id is worse than none.
+        Err(E::invalid_type(de::Unexpected::Float(value), &self))
+    }
+}
+
+struct DecimalOrString;
+
+impl<'de> Visitor<'de> for DecimalOrString {
+    type Value = Decimal;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a decimal number or a numeric string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Decimal, E> {
+        let value = value.trim().replace(',', "");
+
+        Decimal::from_str(&value)
+            .or_else(|_| Decimal::from_scientific(&value))
+            .map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Decimal, E> {
+        Ok(Decimal::from(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Decimal, E> {
+        Ok(Decimal::from(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Decimal, E> {
+        // The shortest representation of the float is what termii meant, `0.1` and not
+        // `0.1000000000000000055511151231`.
+        self.visit_str(&value.to_string())
+    }
+}
+
+struct IntegerOrString<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for IntegerOrString<T>
+where
+    T: FromStr + TryFrom<i64> + TryFrom<u64>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer or a numeric string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        T::from_str(value.trim()).map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        T::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        T::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+}
+
+struct BoolOrInt;
+
+impl<'de> Visitor<'de> for BoolOrInt {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a bool, 0 or 1")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<bool, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<bool, E> {
+        Ok(value != 0)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<bool, E> {
+        Ok(value != 0)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<bool, E> {
+        match value.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" => Ok(true),
+            "false" | "0" | "no" | "" => Ok(false),
+            _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+        }
+    }
+}
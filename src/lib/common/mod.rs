@@ -1,5 +1,15 @@
 pub mod errors;
 pub mod insights;
+mod lenient;
+pub mod money;
+pub mod outbox;
 pub mod pagination;
 pub mod switch;
 pub mod token;
+pub mod webhooks;
+
+use std::time::Duration;
+
+/// The shortest wait between requests of the helpers that poll termii, shorter intervals are
+/// raised to it.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
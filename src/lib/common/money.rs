@@ -0,0 +1,118 @@
+//! Decimal amounts of money as reported by termii.
+
+use std::fmt;
+
+pub use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::common::lenient;
+
+/// An ISO 4217 currency code like `NGN`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub struct Currency(String);
+
+impl Currency {
+    pub fn new(code: &str) -> Currency {
+        Currency(code.trim().to_uppercase())
+    }
+
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Currency {
+    fn from(code: String) -> Currency {
+        Currency::new(&code)
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> String {
+        currency.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An amount of money.
+///
+/// Termii sends amounts as JSON numbers or strings depending on the endpoint, both are accepted.
+/// Only the amount is part of the payload, the currency is filled in where the response has one.
+/// Of termii's responses only the balance lookup names a currency, amounts in send responses and
+/// the message history have none.
+/// Amounts serialize as strings so no precision is lost.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::money::{Decimal, Money};
+///
+/// let from_number: Money = serde_json::from_str("1234.5").unwrap();
+/// let from_string: Money = serde_json::from_str("\"1234.50\"").unwrap();
+///
+/// assert_eq!(from_number, from_string);
+/// assert_eq!(from_number.amount, Decimal::new(12345, 1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Money {
+    pub amount: Decimal,
+    pub currency: Option<Currency>,
+}
+
+impl Money {
+    pub fn new(amount: Decimal, currency: Option<Currency>) -> Money {
+        Money { amount, currency }
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Money {
+        self.currency = Some(currency);
+        self
+    }
+}
+
+impl From<Decimal> for Money {
+    fn from(amount: Decimal) -> Money {
+        Money::new(amount, None)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.currency {
+            Some(currency) => write!(f, "{} {}", currency, self.amount),
+            None => write!(f, "{}", self.amount),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D>(deserializer: D) -> Result<Money, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        lenient::decimal_or_string(deserializer).map(Money::from)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Serialize::serialize(&self.amount, serializer)
+    }
+}
+
+/// An optional amount where `null` and empty strings are `None`.
+pub(crate) fn optional_money<'de, D>(deserializer: D) -> Result<Option<Money>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(lenient::optional_decimal_or_string(deserializer)?.map(Money::from))
+}
@@ -0,0 +1,144 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    switch::messaging::{MessageBulkRequest, MessageBulkResponse, MessageRequest, MessageResponse},
+    token::request::{RequestTokenRequest, RequestTokenResponse},
+};
+
+/// The id a store gives an enqueued job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct JobId(pub u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A request waiting in the outbox.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", content = "request", rename_all = "snake_case")]
+pub enum OutboxPayload {
+    Message(MessageRequest),
+    Bulk(MessageBulkRequest),
+    Token(RequestTokenRequest),
+}
+
+impl From<MessageRequest> for OutboxPayload {
+    fn from(message: MessageRequest) -> OutboxPayload {
+        OutboxPayload::Message(message)
+    }
+}
+
+impl From<MessageBulkRequest> for OutboxPayload {
+    fn from(message: MessageBulkRequest) -> OutboxPayload {
+        OutboxPayload::Bulk(message)
+    }
+}
+
+impl From<RequestTokenRequest> for OutboxPayload {
+    fn from(request: RequestTokenRequest) -> OutboxPayload {
+        OutboxPayload::Token(request)
+    }
+}
+
+/// What termii answered to a job's request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", content = "response", rename_all = "snake_case")]
+pub enum OutboxResponse {
+    Message(MessageResponse),
+    Bulk(MessageBulkResponse),
+    Token(RequestTokenResponse),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum JobState {
+    /// Waiting for its first or next attempt.
+    Pending,
+    Sent {
+        response: OutboxResponse,
+    },
+    /// Failed permanently or ran out of attempts, it won't be retried.
+    DeadLettered {
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OutboxJob {
+    pub id: JobId,
+    pub payload: OutboxPayload,
+    pub state: JobState,
+    pub attempts: u32,
+    /// Unix timestamp (milliseconds) of the next attempt.
+    pub next_attempt_at: u64,
+    pub last_error: Option<String>,
+}
+
+impl OutboxJob {
+    /// The response of a sent job.
+    pub fn response(&self) -> Option<&OutboxResponse> {
+        match &self.state {
+            JobState::Sent { response } => Some(response),
+            _ => None,
+        }
+    }
+}
+
+/// How often and how far apart failed sends are retried.
+///
+/// Failures that can't succeed on a retry, like a rejected sender ID, are dead-lettered on the
+/// first attempt.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The wait before the attempt after `attempts` failed ones, doubling every time.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use termii_rust::common::outbox::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::default();
+    ///
+    /// assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    /// assert_eq!(policy.backoff(3), Duration::from_secs(4));
+    /// assert_eq!(policy.backoff(20), Duration::from_secs(5 * 60));
+    /// ```
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+pub(crate) fn unix_now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,22 @@
+//! Types for the outbox, a persistent queue of sends retried until termii accepts them.
+//!
+//! Requests are enqueued into an [`OutboxStore`] and sent by the
+//! [`OutboxWorker`](crate::async_impl::rest::outbox::OutboxWorker). A SQLite store is available
+//! behind the `outbox-sqlite` feature.
+//!
+//! Delivery is at least once. Connection failures, rate limiting and server errors are retried,
+//! but termii may have accepted a request before such a failure was reported, so a recipient can
+//! get the same message or token more than once. Queue sends that must go out exactly once with
+//! [`Messaging::send_idempotent`](crate::async_impl::rest::switch::messaging::Messaging::send_idempotent)
+//! instead.
+
+pub mod job;
+pub use job::*;
+
+pub mod store;
+pub use store::*;
+
+#[cfg(feature = "outbox-sqlite")]
+pub mod sqlite;
+#[cfg(feature = "outbox-sqlite")]
+pub use sqlite::*;
@@ -0,0 +1,264 @@
+//! An [`OutboxStore`] backed by SQLite, so queued sends survive a restart.
+
+use std::{path::Path, sync::Mutex};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::common::{
+    errors::OutboxError,
+    outbox::{
+        job::{JobId, JobState, OutboxJob, OutboxPayload, OutboxResponse},
+        store::OutboxStore,
+    },
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS termii_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL,
+    state TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at INTEGER NOT NULL,
+    last_error TEXT
+);
+CREATE INDEX IF NOT EXISTS termii_outbox_status ON termii_outbox (status, next_attempt_at);
+";
+
+const COLUMNS: &str = "id, payload, state, attempts, next_attempt_at, last_error";
+
+/// Jobs are kept in the `termii_outbox` table, created when the store is opened.
+///
+/// The state of a job is kept in an indexed `status` column next to its details, so polling for
+/// due jobs doesn't scan the sent ones. Sent jobs stay in the table until
+/// [`purge_sent`](OutboxStore::purge_sent) removes them.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{
+///     outbox::{OutboxStore, SqliteOutboxStore},
+///     switch::messaging::{Channel, MessageRequest, MessageType},
+/// };
+///
+/// let store = SqliteOutboxStore::open_in_memory().unwrap();
+///
+/// let message = MessageRequest::new(
+///     "234XXXXXXXXXX".to_string(),
+///     "YourOrg".to_string(),
+///     "Your message".to_string(),
+///     MessageType::Plain,
+///     Channel::Generic,
+/// );
+///
+/// let id = store.enqueue(message.into(), 0).unwrap();
+///
+/// assert_eq!(store.due(0, 10).unwrap()[0].id, id);
+/// ```
+#[derive(Debug)]
+pub struct SqliteOutboxStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteOutboxStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SqliteOutboxStore, OutboxError> {
+        SqliteOutboxStore::from_connection(Connection::open(path).map_err(storage)?)
+    }
+
+    pub fn open_in_memory() -> Result<SqliteOutboxStore, OutboxError> {
+        SqliteOutboxStore::from_connection(Connection::open_in_memory().map_err(storage)?)
+    }
+
+    pub fn from_connection(connection: Connection) -> Result<SqliteOutboxStore, OutboxError> {
+        connection.execute_batch(SCHEMA).map_err(storage)?;
+
+        Ok(SqliteOutboxStore {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn update(
+        &self,
+        id: JobId,
+        state: &JobState,
+        attempts: u32,
+        next_attempt_at: Option<u64>,
+        error: Option<&str>,
+    ) -> Result<(), OutboxError> {
+        let status = status(state);
+        let state = serde_json::to_string(state).map_err(storage)?;
+
+        let updated = self
+            .lock()
+            .execute(
+                "UPDATE termii_outbox
+                 SET status = ?2, state = ?3, attempts = ?4,
+                     next_attempt_at = COALESCE(?5, next_attempt_at),
+                     last_error = COALESCE(?6, last_error)
+                 WHERE id = ?1",
+                params![
+                    id.0 as i64,
+                    status,
+                    state,
+                    attempts,
+                    next_attempt_at.map(|at| at as i64),
+                    error
+                ],
+            )
+            .map_err(storage)?;
+
+        if updated == 0 {
+            return Err(OutboxError::JobNotFound(id));
+        }
+
+        Ok(())
+    }
+
+    fn select(&self, filter: &str, values: &[i64]) -> Result<Vec<OutboxJob>, OutboxError> {
+        let connection = self.lock();
+        let mut statement = connection
+            .prepare(&format!("SELECT {} FROM termii_outbox {}", COLUMNS, filter))
+            .map_err(storage)?;
+
+        let rows = statement
+            .query_map(rusqlite::params_from_iter(values), read_row)
+            .map_err(storage)?;
+
+        rows.map(|row| into_job(row.map_err(storage)?))
+            .collect::<Result<Vec<OutboxJob>, OutboxError>>()
+    }
+}
+
+impl OutboxStore for SqliteOutboxStore {
+    fn enqueue(&self, payload: OutboxPayload, now: u64) -> Result<JobId, OutboxError> {
+        let payload = serde_json::to_string(&payload).map_err(storage)?;
+        let state = &JobState::Pending;
+
+        let connection = self.lock();
+        connection
+            .execute(
+                "INSERT INTO termii_outbox (payload, status, state, next_attempt_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    payload,
+                    status(state),
+                    serde_json::to_string(state).map_err(storage)?,
+                    now as i64
+                ],
+            )
+            .map_err(storage)?;
+
+        Ok(JobId(connection.last_insert_rowid() as u64))
+    }
+
+    fn get(&self, id: JobId) -> Result<Option<OutboxJob>, OutboxError> {
+        let connection = self.lock();
+
+        let row = connection
+            .query_row(
+                &format!("SELECT {} FROM termii_outbox WHERE id = ?1", COLUMNS),
+                params![id.0 as i64],
+                read_row,
+            )
+            .optional()
+            .map_err(storage)?;
+
+        row.map(into_job).transpose()
+    }
+
+    fn due(&self, now: u64, limit: usize) -> Result<Vec<OutboxJob>, OutboxError> {
+        self.select(
+            "WHERE status = 'pending' AND next_attempt_at <= ?1
+             ORDER BY id LIMIT ?2",
+            &[now as i64, limit as i64],
+        )
+    }
+
+    fn mark_sent(
+        &self,
+        id: JobId,
+        attempts: u32,
+        response: OutboxResponse,
+    ) -> Result<(), OutboxError> {
+        self.update(id, &JobState::Sent { response }, attempts, None, None)
+    }
+
+    fn retry_at(
+        &self,
+        id: JobId,
+        attempts: u32,
+        next_attempt_at: u64,
+        error: &str,
+    ) -> Result<(), OutboxError> {
+        self.update(
+            id,
+            &JobState::Pending,
+            attempts,
+            Some(next_attempt_at),
+            Some(error),
+        )
+    }
+
+    fn dead_letter(&self, id: JobId, attempts: u32, error: &str) -> Result<(), OutboxError> {
+        let state = JobState::DeadLettered {
+            error: error.to_string(),
+        };
+
+        self.update(id, &state, attempts, None, Some(error))
+    }
+
+    fn dead_letters(&self) -> Result<Vec<OutboxJob>, OutboxError> {
+        self.select("WHERE status = 'dead_lettered' ORDER BY id", &[])
+    }
+
+    fn purge_sent(&self) -> Result<usize, OutboxError> {
+        self.lock()
+            .execute("DELETE FROM termii_outbox WHERE status = 'sent'", [])
+            .map_err(storage)
+    }
+}
+
+/// The value of the `status` column, the tag `JobState` is serialized with.
+fn status(state: &JobState) -> &'static str {
+    match state {
+        JobState::Pending => "pending",
+        JobState::Sent { .. } => "sent",
+        JobState::DeadLettered { .. } => "dead_lettered",
+    }
+}
+
+type Row = (i64, String, String, u32, i64, Option<String>);
+
+fn read_row(row: &rusqlite::Row) -> rusqlite::Result<Row> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        row.get(5)?,
+    ))
+}
+
+fn into_job(row: Row) -> Result<OutboxJob, OutboxError> {
+    let (id, payload, state, attempts, next_attempt_at, last_error) = row;
+
+    Ok(OutboxJob {
+        id: JobId(id as u64),
+        payload: serde_json::from_str(&payload).map_err(storage)?,
+        state: serde_json::from_str(&state).map_err(storage)?,
+        attempts,
+        next_attempt_at: next_attempt_at as u64,
+        last_error,
+    })
+}
+
+fn storage<E: std::fmt::Display>(err: E) -> OutboxError {
+    OutboxError::Storage(err.to_string())
+}
@@ -0,0 +1,175 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use crate::common::{
+    errors::OutboxError,
+    outbox::job::{JobId, JobState, OutboxJob, OutboxPayload, OutboxResponse},
+};
+
+/// Storage for outbox jobs.
+///
+/// A store is drained by a single worker, jobs are not claimed before they are sent.
+pub trait OutboxStore {
+    /// Store a pending job due right away.
+    fn enqueue(&self, payload: OutboxPayload, now: u64) -> Result<JobId, OutboxError>;
+
+    fn get(&self, id: JobId) -> Result<Option<OutboxJob>, OutboxError>;
+
+    /// Pending jobs whose next attempt is due at `now`, oldest first.
+    fn due(&self, now: u64, limit: usize) -> Result<Vec<OutboxJob>, OutboxError>;
+
+    fn mark_sent(
+        &self,
+        id: JobId,
+        attempts: u32,
+        response: OutboxResponse,
+    ) -> Result<(), OutboxError>;
+
+    fn retry_at(
+        &self,
+        id: JobId,
+        attempts: u32,
+        next_attempt_at: u64,
+        error: &str,
+    ) -> Result<(), OutboxError>;
+
+    fn dead_letter(&self, id: JobId, attempts: u32, error: &str) -> Result<(), OutboxError>;
+
+    fn dead_letters(&self) -> Result<Vec<OutboxJob>, OutboxError>;
+
+    /// Delete the jobs that were sent, returning how many were deleted.
+    ///
+    /// Their responses can no longer be looked up afterwards, call it once they aren't needed.
+    fn purge_sent(&self) -> Result<usize, OutboxError>;
+}
+
+/// An [`OutboxStore`] that keeps jobs in process memory, they are lost on restart.
+#[derive(Debug, Default)]
+pub struct InMemoryOutboxStore {
+    state: Mutex<InMemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryState {
+    next_id: u64,
+    jobs: BTreeMap<JobId, OutboxJob>,
+}
+
+impl InMemoryOutboxStore {
+    pub fn new() -> InMemoryOutboxStore {
+        InMemoryOutboxStore::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, InMemoryState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn update<F>(&self, id: JobId, update: F) -> Result<(), OutboxError>
+    where
+        F: FnOnce(&mut OutboxJob),
+    {
+        let mut state = self.lock();
+        let job = state
+            .jobs
+            .get_mut(&id)
+            .ok_or(OutboxError::JobNotFound(id))?;
+        update(job);
+        Ok(())
+    }
+}
+
+impl OutboxStore for InMemoryOutboxStore {
+    fn enqueue(&self, payload: OutboxPayload, now: u64) -> Result<JobId, OutboxError> {
+        let mut state = self.lock();
+
+        state.next_id += 1;
+        let id = JobId(state.next_id);
+
+        state.jobs.insert(
+            id,
+            OutboxJob {
+                id,
+                payload,
+                state: JobState::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_error: None,
+            },
+        );
+
+        Ok(id)
+    }
+
+    fn get(&self, id: JobId) -> Result<Option<OutboxJob>, OutboxError> {
+        Ok(self.lock().jobs.get(&id).cloned())
+    }
+
+    fn due(&self, now: u64, limit: usize) -> Result<Vec<OutboxJob>, OutboxError> {
+        Ok(self
+            .lock()
+            .jobs
+            .values()
+            .filter(|job| matches!(job.state, JobState::Pending) && job.next_attempt_at <= now)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn mark_sent(
+        &self,
+        id: JobId,
+        attempts: u32,
+        response: OutboxResponse,
+    ) -> Result<(), OutboxError> {
+        self.update(id, |job| {
+            job.attempts = attempts;
+            job.state = JobState::Sent { response };
+        })
+    }
+
+    fn retry_at(
+        &self,
+        id: JobId,
+        attempts: u32,
+        next_attempt_at: u64,
+        error: &str,
+    ) -> Result<(), OutboxError> {
+        self.update(id, |job| {
+            job.attempts = attempts;
+            job.next_attempt_at = next_attempt_at;
+            job.last_error = Some(error.to_string());
+        })
+    }
+
+    fn dead_letter(&self, id: JobId, attempts: u32, error: &str) -> Result<(), OutboxError> {
+        self.update(id, |job| {
+            job.attempts = attempts;
+            job.last_error = Some(error.to_string());
+            job.state = JobState::DeadLettered {
+                error: error.to_string(),
+            };
+        })
+    }
+
+    fn dead_letters(&self) -> Result<Vec<OutboxJob>, OutboxError> {
+        Ok(self
+            .lock()
+            .jobs
+            .values()
+            .filter(|job| matches!(job.state, JobState::DeadLettered { .. }))
+            .cloned()
+            .collect())
+    }
+
+    fn purge_sent(&self) -> Result<usize, OutboxError> {
+        let mut state = self.lock();
+        let before = state.jobs.len();
+
+        state
+            .jobs
+            .retain(|_, job| !matches!(job.state, JobState::Sent { .. }));
+
+        Ok(before - state.jobs.len())
+    }
+}
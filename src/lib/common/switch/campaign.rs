@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::common::lenient;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PhoneBookListResponse {
     pub data: Vec<PhoneBookItem>,
@@ -9,8 +11,10 @@ pub struct PhoneBookListResponse {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PhoneBookItem {
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub id: String,
     pub name: String,
+    #[serde(deserialize_with = "lenient::integer_or_string")]
     pub total_number_of_contacts: i64,
     pub date_created: String,
     pub last_updated: String,
@@ -20,7 +24,9 @@ pub struct PhoneBookItem {
 pub struct Links {
     pub first: String,
     pub last: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub prev: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub next: Option<String>,
 }
 
@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::common::{
+    money::{Currency, Decimal},
+    switch::messaging::{Channel, MessageBulkRequest, MessageRequest},
+};
+
+/// Characters of the GSM 03.38 alphabet, everything else forces UCS-2 encoding.
+const GSM_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+/// Characters sent as an escape sequence, taking two septets.
+const GSM_EXTENDED: &str = "^{}\\[~]|€\u{c}";
+
+/// The name used for recipients whose network is not known from the price table prefixes.
+pub const UNKNOWN_NETWORK: &str = "unknown";
+
+/// Number of SMS segments `text` is split into.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::switch::cost::sms_segments;
+///
+/// assert_eq!(sms_segments(&"a".repeat(160)), 1);
+/// assert_eq!(sms_segments(&"a".repeat(161)), 2);
+/// assert_eq!(sms_segments("Hello from Rust Termii. 😎"), 1);
+/// assert_eq!(sms_segments(&"😎".repeat(40)), 2);
+/// ```
+pub fn sms_segments(text: &str) -> usize {
+    let gsm_length = text.chars().try_fold(0usize, |length, c| {
+        if GSM_BASIC.contains(c) {
+            Some(length + 1)
+        } else if GSM_EXTENDED.contains(c) {
+            Some(length + 2)
+        } else {
+            None
+        }
+    });
+
+    let (length, single, multi) = match gsm_length {
+        Some(length) => (length, 160, 153),
+        None => (text.encode_utf16().count(), 70, 67),
+    };
+
+    if length <= single {
+        1
+    } else {
+        length.div_ceil(multi)
+    }
+}
+
+/// Prices per SMS segment by channel and network, all in one currency.
+#[derive(Debug, Clone)]
+pub struct PriceTable {
+    currency: Currency,
+    default_price: Decimal,
+    channel_prices: HashMap<Channel, Decimal>,
+    network_prices: HashMap<(Channel, String), Decimal>,
+    prefixes: Vec<(String, String)>,
+}
+
+impl PriceTable {
+    /// A table charging `default_price` per segment when nothing more specific is set.
+    pub fn new(currency: Currency, default_price: Decimal) -> PriceTable {
+        PriceTable {
+            currency,
+            default_price,
+            channel_prices: HashMap::new(),
+            network_prices: HashMap::new(),
+            prefixes: Vec::new(),
+        }
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    pub fn channel_price(mut self, channel: Channel, price: Decimal) -> PriceTable {
+        self.channel_prices.insert(channel, price);
+        self
+    }
+
+    pub fn network_price(mut self, channel: Channel, network: &str, price: Decimal) -> PriceTable {
+        self.network_prices
+            .insert((channel, network.to_string()), price);
+        self
+    }
+
+    /// Recipients whose number starts with `prefix` are on `network`.
+    pub fn prefix(mut self, prefix: &str, network: &str) -> PriceTable {
+        self.prefixes
+            .push((prefix.to_string(), network.to_string()));
+        self.prefixes
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    /// The network of a phone number from the longest matching prefix.
+    pub fn network_for(&self, phone_number: &str) -> Option<&str> {
+        let phone_number = phone_number.trim_start_matches('+');
+
+        self.prefixes
+            .iter()
+            .find(|(prefix, _)| phone_number.starts_with(prefix.as_str()))
+            .map(|(_, network)| network.as_str())
+    }
+
+    pub fn price_for(&self, channel: &Channel, network: &str) -> Decimal {
+        self.network_prices
+            .get(&(channel.clone(), network.to_string()))
+            .or_else(|| self.channel_prices.get(channel))
+            .copied()
+            .unwrap_or(self.default_price)
+    }
+
+    /// Estimate the cost of sending a message.
+    pub fn estimate(&self, message: &MessageRequest) -> CostEstimate {
+        self.estimate_for([message.to.as_str()], &message.sms, &message.channel)
+    }
+
+    /// Estimate the cost of sending a bulk message.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::{
+    ///     money::{Currency, Decimal},
+    ///     switch::{
+    ///         cost::PriceTable,
+    ///         messaging::{Channel, MessageBulkRequest, MessageType},
+    ///     },
+    /// };
+    ///
+    /// let prices = PriceTable::new(Currency::new("NGN"), Decimal::from(4))
+    ///     .prefix("234803", "MTN")
+    ///     .network_price(Channel::Generic, "MTN", Decimal::new(25, 1));
+    ///
+    /// let message = MessageBulkRequest::new(
+    ///     vec!["2348030000000".to_string(), "2348120000000".to_string()],
+    ///     "Your org sender id".to_string(),
+    ///     "Your message".to_string(),
+    ///     MessageType::Plain,
+    ///     Channel::Generic,
+    /// );
+    ///
+    /// let estimate = prices.estimate_bulk(&message);
+    ///
+    /// assert_eq!(estimate.per_network["MTN"].cost, Decimal::new(25, 1));
+    /// assert_eq!(estimate.total, Decimal::new(65, 1));
+    /// ```
+    pub fn estimate_bulk(&self, message: &MessageBulkRequest) -> CostEstimate {
+        self.estimate_for(
+            message.to.iter().map(String::as_str),
+            &message.sms,
+            &message.channel,
+        )
+    }
+
+    pub fn estimate_for<'r, I>(&self, recipients: I, sms: &str, channel: &Channel) -> CostEstimate
+    where
+        I: IntoIterator<Item = &'r str>,
+    {
+        let segments = sms_segments(sms);
+
+        let mut estimate = CostEstimate {
+            currency: self.currency.clone(),
+            segments,
+            recipients: 0,
+            per_network: BTreeMap::new(),
+            total: Decimal::ZERO,
+        };
+
+        for recipient in recipients {
+            let network = self.network_for(recipient).unwrap_or(UNKNOWN_NETWORK);
+            let cost = self.price_for(channel, network) * Decimal::from(segments);
+
+            let network_cost = estimate.per_network.entry(network.to_string()).or_default();
+            network_cost.recipients += 1;
+            network_cost.cost += cost;
+
+            estimate.recipients += 1;
+            estimate.total += cost;
+        }
+
+        estimate
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkCost {
+    pub recipients: usize,
+    pub cost: Decimal,
+}
+
+/// What a send is expected to cost before it is made.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostEstimate {
+    pub currency: Currency,
+    /// Segments per recipient.
+    pub segments: usize,
+    pub recipients: usize,
+    pub per_network: BTreeMap<String, NetworkCost>,
+    pub total: Decimal,
+}
@@ -0,0 +1,244 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::common::errors::{HttpError, SendError};
+
+/// What [`IdempotencyStore::try_begin`] found under a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Begin<T> {
+    /// The key was free and is now reserved for the caller's send.
+    New,
+    /// Another send reserved the key and has not finished.
+    InFlight,
+    /// A send with the key succeeded with this response.
+    Done(T),
+}
+
+/// Remembers the sends made under the caller's idempotency keys.
+///
+/// Termii has no idempotency header, a send first reserves its key with `try_begin` and then
+/// either `complete`s it with the response or `release`s it so the send can be retried. A retry
+/// arriving while the first send is in flight finds the reservation and doesn't message the
+/// recipient again. Implement this to share keys across processes, `try_begin` must check and
+/// reserve in one atomic step, such as a Redis `SET NX`.
+pub trait IdempotencyStore<T> {
+    fn try_begin(&self, key: &str) -> Begin<T>;
+
+    fn complete(&self, key: &str, response: T);
+
+    fn release(&self, key: &str);
+}
+
+#[derive(Debug)]
+enum Entry<T> {
+    InFlight,
+    Done(T),
+}
+
+/// An [`IdempotencyStore`] that keeps keys in process memory for a fixed window.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use termii_rust::common::switch::idempotency::{
+///     Begin, IdempotencyStore, InMemoryIdempotencyStore,
+/// };
+///
+/// let store = InMemoryIdempotencyStore::new(Duration::from_secs(24 * 60 * 60));
+///
+/// assert_eq!(store.try_begin("order-1042-shipped"), Begin::New);
+/// assert_eq!(store.try_begin("order-1042-shipped"), Begin::InFlight);
+///
+/// store.complete("order-1042-shipped", "3017544054459309654184010".to_string());
+///
+/// assert_eq!(
+///     store.try_begin("order-1042-shipped"),
+///     Begin::Done("3017544054459309654184010".to_string())
+/// );
+///
+/// assert_eq!(store.try_begin("order-1043-shipped"), Begin::New);
+/// store.release("order-1043-shipped");
+/// assert_eq!(store.try_begin("order-1043-shipped"), Begin::New);
+/// ```
+#[derive(Debug)]
+pub struct InMemoryIdempotencyStore<T> {
+    window: Duration,
+    entries: Mutex<HashMap<String, (Instant, Entry<T>)>>,
+}
+
+impl<T: Clone> InMemoryIdempotencyStore<T> {
+    pub fn new(window: Duration) -> InMemoryIdempotencyStore<T> {
+        InMemoryIdempotencyStore {
+            window,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Drop the keys older than the window.
+    pub fn purge_expired(&self) {
+        let window = self.window;
+        self.lock()
+            .retain(|_, (stored_at, _)| stored_at.elapsed() < window);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Entry<T>)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T: Clone> IdempotencyStore<T> for InMemoryIdempotencyStore<T> {
+    fn try_begin(&self, key: &str) -> Begin<T> {
+        let mut entries = self.lock();
+
+        match entries.get(key) {
+            Some((stored_at, Entry::InFlight)) if stored_at.elapsed() < self.window => {
+                Begin::InFlight
+            }
+            Some((stored_at, Entry::Done(response))) if stored_at.elapsed() < self.window => {
+                Begin::Done(response.clone())
+            }
+            _ => {
+                entries.insert(key.to_string(), (Instant::now(), Entry::InFlight));
+                Begin::New
+            }
+        }
+    }
+
+    fn complete(&self, key: &str, response: T) {
+        self.lock()
+            .insert(key.to_string(), (Instant::now(), Entry::Done(response)));
+    }
+
+    fn release(&self, key: &str) {
+        let mut entries = self.lock();
+
+        if let Some((_, Entry::InFlight)) = entries.get(key) {
+            entries.remove(key);
+        }
+    }
+}
+
+/// Reserve `key`, `Some` with the stored response when the send already succeeded.
+pub(crate) fn begin_send<T, S>(store: &S, key: &str) -> Result<Option<T>, SendError>
+where
+    S: IdempotencyStore<T> + ?Sized,
+{
+    match store.try_begin(key) {
+        Begin::New => Ok(None),
+        Begin::InFlight => Err(SendError::InFlight(key.to_string())),
+        Begin::Done(response) => Ok(Some(response)),
+    }
+}
+
+/// Record the outcome of the send that reserved `key`.
+///
+/// The key is only released when termii certainly rejected the send. A connection error, server
+/// error or unreadable success response may come after termii accepted the message, the key then
+/// stays reserved until the window ends rather than letting a retry message the recipient again.
+pub(crate) fn finish_send<T, S>(
+    store: &S,
+    key: &str,
+    result: Result<T, HttpError>,
+) -> Result<T, SendError>
+where
+    T: Clone,
+    S: IdempotencyStore<T> + ?Sized,
+{
+    match result {
+        Ok(response) => {
+            store.complete(key, response.clone());
+            Ok(response)
+        }
+        Err(err) => {
+            if err.is_rejected() {
+                store.release(key);
+            }
+            Err(err.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> InMemoryIdempotencyStore<String> {
+        InMemoryIdempotencyStore::new(Duration::from_secs(60))
+    }
+
+    fn json_error(status: usize) -> HttpError {
+        HttpError::JsonError {
+            status,
+            message: String::new(),
+        }
+    }
+
+    fn finish(store: &InMemoryIdempotencyStore<String>, result: Result<String, HttpError>) {
+        assert_eq!(begin_send(store, "key").unwrap(), None);
+        let _ = finish_send(store, "key", result);
+    }
+
+    #[test]
+    fn success_is_stored() {
+        let store = store();
+        finish(&store, Ok("message-id".to_string()));
+
+        assert_eq!(
+            store.try_begin("key"),
+            Begin::Done("message-id".to_string())
+        );
+    }
+
+    #[test]
+    fn rejection_releases_the_key() {
+        for status in [400, 401, 404, 422] {
+            let store = store();
+            finish(&store, Err(json_error(status)));
+
+            assert_eq!(store.try_begin("key"), Begin::New, "status {}", status);
+        }
+    }
+
+    #[test]
+    fn timeout_and_rate_limit_keep_the_key() {
+        for status in [408, 429] {
+            let store = store();
+            finish(&store, Err(json_error(status)));
+
+            assert_eq!(store.try_begin("key"), Begin::InFlight, "status {}", status);
+        }
+    }
+
+    #[test]
+    fn server_error_keeps_the_key() {
+        let store = store();
+        finish(&store, Err(json_error(502)));
+
+        assert_eq!(store.try_begin("key"), Begin::InFlight);
+    }
+
+    #[test]
+    fn unreadable_success_keeps_the_key() {
+        let store = store();
+        finish(&store, Err(json_error(200)));
+
+        assert_eq!(store.try_begin("key"), Begin::InFlight);
+    }
+
+    #[test]
+    fn connection_error_keeps_the_key() {
+        let err = reqwest::Client::new().get("not a url").build().unwrap_err();
+
+        let store = store();
+        finish(&store, Err(HttpError::Io(err)));
+
+        assert_eq!(store.try_begin("key"), Begin::InFlight);
+    }
+}
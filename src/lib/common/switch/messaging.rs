@@ -1,24 +1,58 @@
-use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize, Serialize)]
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    errors::{HttpError, TemplateError, ValidationError},
+    lenient,
+    money::Money,
+    switch::{cost::sms_segments, sender_id::validate_sender, templates::TemplateVariables},
+};
+
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{money::Decimal, switch::messaging::MessageResponse};
+///
+/// let numeric: MessageResponse = serde_json::from_str(
+///     r#"{"message_id": 9122821270554876574, "message": "Successfully Sent", "balance": 9, "user": "Peter Mcleish"}"#,
+/// )
+/// .unwrap();
+///
+/// let string: MessageResponse = serde_json::from_str(
+///     r#"{"code": "ok", "message_id": "9122821270554876574", "message": "Successfully Sent", "balance": "9.00", "user": "Peter Mcleish"}"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(numeric.message_id, string.message_id);
+/// assert_eq!(numeric.balance.amount, Decimal::from(9));
+/// assert_eq!(numeric.code, None);
+///
+/// // Wider than a u64, only a string keeps every digit of the id.
+/// let too_wide = serde_json::from_str::<MessageResponse>(
+///     r#"{"message_id": 3017544054459309654184010, "message": "Successfully Sent", "balance": 9, "user": "Peter Mcleish"}"#,
+/// );
+/// assert!(too_wide.is_err());
+///
+/// let wide: MessageResponse = serde_json::from_str(
+///     r#"{"message_id": "3017544054459309654184010", "message": "Successfully Sent", "balance": 9, "user": "Peter Mcleish"}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(wide.message_id, "3017544054459309654184010");
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MessageResponse {
-    #[serde(deserialize_with = "from_usize")]
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub message_id: String,
     pub message: String,
-    pub balance: f64,
+    /// The balance left, termii doesn't say in which currency so `currency` is `None`.
+    pub balance: Money,
     pub user: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub code: Option<String>,
 }
 
-fn from_usize<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: usize = Deserialize::deserialize(deserializer)?;
-    Ok(s.to_string())
-}
-
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageRequest {
     pub to: String,
     pub from: String,
@@ -49,12 +83,37 @@ impl MessageRequest {
         }
     }
 
+    /// Check `from` is a valid sender ID, whatsapp messages are sent from a number and skipped.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::switch::messaging::{Channel, MessageRequest, MessageType};
+    ///
+    /// let message = MessageRequest::new(
+    ///     "234XXXXXXXXXX".to_string(),
+    ///     "Your org sender id".to_string(),
+    ///     "Your message".to_string(),
+    ///     MessageType::Plain,
+    ///     Channel::Generic,
+    /// );
+    ///
+    /// assert!(message.validate_sender().is_err());
+    /// ```
+    pub fn validate_sender(&self) -> Result<(), ValidationError> {
+        if self.channel == Channel::Whatsapp {
+            return Ok(());
+        }
+
+        validate_sender("from", &self.from)
+    }
+
     pub(crate) fn set_api_key(&mut self, api_key: &str) {
         self.api_key = Some(api_key.to_string());
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageBulkRequest {
     pub to: Vec<String>,
     pub from: String,
@@ -83,38 +142,271 @@ impl MessageBulkRequest {
         }
     }
 
+    /// Check `from` is a valid sender ID, like [`MessageRequest::validate_sender`].
+    pub fn validate_sender(&self) -> Result<(), ValidationError> {
+        if self.channel == Channel::Whatsapp {
+            return Ok(());
+        }
+
+        validate_sender("from", &self.from)
+    }
+
     pub(crate) fn set_api_key(&mut self, api_key: &str) {
         self.api_key = Some(api_key.to_string());
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageBulkResponse {
     pub code: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub message_id: String,
     pub message: String,
-    pub balance: f64,
+    /// The balance left, termii doesn't say in which currency so `currency` is `None`.
+    pub balance: Money,
     pub user: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Media {
     pub url: String,
     pub caption: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub enum MessageType {
-    #[serde(rename = "plain")]
-    Plain,
+string_enum! {
+    /// The kind of message, termii only documents `plain` for now.
+    pub enum MessageType {
+        Plain = "plain",
+    }
+}
+
+string_enum! {
+    /// The route a message is sent on.
+    ///
+    /// Channels termii adds later deserialize to [`Channel::Unknown`] and serialize back unchanged.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::switch::messaging::Channel;
+    ///
+    /// let channel: Channel = serde_json::from_str(r#""voice""#).unwrap();
+    ///
+    /// assert_eq!(channel, Channel::Unknown("voice".to_string()));
+    /// assert_eq!(serde_json::to_string(&channel).unwrap(), r#""voice""#);
+    /// assert_eq!(serde_json::from_str::<Channel>(r#""dnd""#).unwrap(), Channel::Dnd);
+    /// ```
+    #[derive(Hash)]
+    pub enum Channel {
+        Generic = "generic",
+        Dnd = "dnd",
+        Whatsapp = "whatsapp",
+    }
+}
+
+impl Channel {
+    /// Whether the channel is picked from the recipient's DND status, only generic and dnd are.
+    pub fn follows_dnd_status(&self) -> bool {
+        matches!(self, Channel::Generic | Channel::Dnd)
+    }
+
+    /// The channel that reaches a number with the given DND status.
+    ///
+    /// Generic messages to DND active numbers are dropped by the carriers, other channels are
+    /// left as is.
+    pub fn for_dnd_status(&self, dnd_active: bool) -> Channel {
+        match self {
+            Channel::Generic | Channel::Dnd if dnd_active => Channel::Dnd,
+            Channel::Generic | Channel::Dnd => Channel::Generic,
+            other => other.clone(),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub enum Channel {
-    #[serde(rename = "generic")]
-    Generic,
-    #[serde(rename = "dnd")]
-    Dnd,
-    #[serde(rename = "whatsapp")]
-    Whatsapp,
+/// A message sent with the channel picked from the recipient's DND status.
+#[derive(Debug, Clone)]
+pub struct RoutedMessageResponse {
+    pub channel: Channel,
+    pub response: MessageResponse,
+}
+
+/// One of the bulk requests a DND aware bulk send was split into, with its own result so a
+/// failed channel can be retried without messaging the other one again.
+#[derive(Debug)]
+pub struct RoutedBulkResponse {
+    pub channel: Channel,
+    pub to: Vec<String>,
+    pub result: Result<MessageBulkResponse, HttpError>,
+}
+
+/// A message body with `{{variable}}` placeholders, rendered locally before sending.
+///
+/// Whitespace inside the braces is ignored and names may use letters, digits, `_`, `-` and `.`.
+/// Write `\{{` for a literal `{{`. Values are inserted as is.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{
+///     errors::TemplateError,
+///     switch::{messaging::MessageTemplate, templates::TemplateVariables},
+/// };
+///
+/// let template = MessageTemplate::parse("Hi {{name}}, your order {{ id }} shipped. \\{{not a placeholder}}").unwrap();
+///
+/// let mut variables = TemplateVariables::new();
+/// variables.insert("name".to_string(), "Ada".to_string());
+///
+/// assert_eq!(template.render(&variables), Err(TemplateError::MissingVariable("id".to_string())));
+///
+/// variables.insert("id".to_string(), "1042".to_string());
+///
+/// assert_eq!(
+///     template.render(&variables).unwrap(),
+///     "Hi Ada, your order 1042 shipped. {{not a placeholder}}"
+/// );
+/// assert_eq!(template.segments(&variables).unwrap(), 1);
+/// assert_eq!(template.variables().collect::<Vec<_>>(), ["name", "id"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Variable(String),
+}
+
+impl MessageTemplate {
+    pub fn parse(source: &str) -> Result<MessageTemplate, TemplateError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            let position = source.len() - rest.len() + start;
+
+            if rest[..start].ends_with('\\') {
+                text.push_str(&rest[..start - 1]);
+                text.push_str("{{");
+                rest = &rest[start + 2..];
+                continue;
+            }
+
+            text.push_str(&rest[..start]);
+
+            let end = rest[start..]
+                .find("}}")
+                .ok_or(TemplateError::UnclosedPlaceholder(position))?;
+            let name = rest[start + 2..start + end].trim();
+
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            {
+                return Err(TemplateError::InvalidPlaceholder {
+                    name: name.to_string(),
+                    position,
+                });
+            }
+
+            if !text.is_empty() {
+                parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+            }
+            parts.push(TemplatePart::Variable(name.to_string()));
+
+            rest = &rest[start + end + 2..];
+        }
+
+        text.push_str(rest);
+
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+
+        Ok(MessageTemplate { parts })
+    }
+
+    /// The variable names in order of first use.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        let mut seen = Vec::new();
+
+        self.parts.iter().filter_map(move |part| match part {
+            TemplatePart::Variable(name) if !seen.contains(&name) => {
+                seen.push(name);
+                Some(name.as_str())
+            }
+            _ => None,
+        })
+    }
+
+    pub fn render(&self, variables: &TemplateVariables) -> Result<String, TemplateError> {
+        let mut rendered = String::new();
+
+        for part in &self.parts {
+            match part {
+                TemplatePart::Text(text) => rendered.push_str(text),
+                TemplatePart::Variable(name) => rendered.push_str(
+                    variables
+                        .get(name)
+                        .ok_or_else(|| TemplateError::MissingVariable(name.clone()))?,
+                ),
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    /// Number of SMS segments the rendered message is sent as.
+    pub fn segments(&self, variables: &TemplateVariables) -> Result<usize, TemplateError> {
+        self.render(variables)
+            .map(|rendered| sms_segments(&rendered))
+    }
+
+    /// Render a body per recipient and group recipients that got the same body.
+    ///
+    /// Groups are in order of their first recipient.
+    pub fn render_grouped<I, N>(
+        &self,
+        recipients: I,
+    ) -> Result<Vec<(String, Vec<String>)>, TemplateError>
+    where
+        I: IntoIterator<Item = (N, TemplateVariables)>,
+        N: Into<String>,
+    {
+        let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+
+        for (phone_number, variables) in recipients {
+            let phone_number = phone_number.into();
+
+            let sms = self
+                .render(&variables)
+                .map_err(|source| TemplateError::Recipient {
+                    phone_number: phone_number.clone(),
+                    source: Box::new(source),
+                })?;
+
+            match index.get(&sms) {
+                Some(&group) => groups[group].1.push(phone_number),
+                None => {
+                    index.insert(sms.clone(), groups.len());
+                    groups.push((sms, vec![phone_number]));
+                }
+            }
+        }
+
+        Ok(groups)
+    }
+}
+
+/// One bulk request of a personalized send, covering the recipients that got the same body.
+#[derive(Debug)]
+pub struct PersonalizedResponse {
+    pub sms: String,
+    pub to: Vec<String>,
+    pub result: Result<MessageBulkResponse, HttpError>,
 }
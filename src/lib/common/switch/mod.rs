@@ -12,3 +12,9 @@ pub use number::*;
 
 pub mod messaging;
 pub use messaging::*;
+
+pub mod cost;
+pub use cost::*;
+
+pub mod idempotency;
+pub use idempotency::*;
@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use crate::common::{lenient, money::Money};
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct NumberMessageResponse {
     pub code: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub message_id: String,
     pub message: String,
-    pub balance: f64,
+    /// The balance left, termii doesn't say in which currency so `currency` is `None`.
+    pub balance: Money,
     pub user: String,
 }
 
@@ -1,5 +1,67 @@
+use std::{
+    collections::HashSet,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
 use serde::{Deserialize, Serialize};
 
+use crate::common::{errors::ValidationError, lenient};
+
+/// Shortest and longest sender ID termii accepts, in characters.
+pub const SENDER_ID_LENGTH: (usize, usize) = (3, 11);
+
+/// Check a sender ID against termii's rules before using it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{errors::ValidationError, switch::sender_id::validate_sender_id};
+///
+/// assert!(validate_sender_id("ACME Key").is_ok());
+/// assert!(matches!(
+///     validate_sender_id("ACME Newsletter"),
+///     Err(ValidationError::OutOfRange { field: "sender_id", .. })
+/// ));
+/// assert!(matches!(
+///     validate_sender_id("ACME!"),
+///     Err(ValidationError::InvalidCharacters { .. })
+/// ));
+/// ```
+pub fn validate_sender_id(sender_id: &str) -> Result<(), ValidationError> {
+    validate_sender("sender_id", sender_id)
+}
+
+pub(crate) fn validate_sender(field: &'static str, sender_id: &str) -> Result<(), ValidationError> {
+    if sender_id.trim().is_empty() {
+        return Err(ValidationError::MissingField(field));
+    }
+
+    let length = sender_id.chars().count();
+    let (min, max) = SENDER_ID_LENGTH;
+
+    if length < min || length > max {
+        return Err(ValidationError::OutOfRange {
+            field,
+            min,
+            max,
+            value: length,
+        });
+    }
+
+    if !sender_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '.' | '_'))
+    {
+        return Err(ValidationError::InvalidCharacters {
+            field,
+            value: sender_id.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SenderIDResponse {
     current_page: i64,
@@ -16,16 +78,52 @@ pub struct SenderIDResponse {
     total: i64,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SenderIDItem {
     pub sender_id: String,
-    pub status: String,
+    pub status: SenderIdStatus,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub company: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub usecase: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
     pub country: Option<String>,
     pub created_at: String,
 }
 
+string_enum! {
+    /// The review state of a sender ID.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::switch::sender_id::{SenderIDItem, SenderIdStatus};
+    ///
+    /// let item: SenderIDItem = serde_json::from_str(
+    ///     r#"{"sender_id": "ACME Key", "status": "unblock", "company": "ACME", "usecase": "", "country": null, "created_at": "2021-03-08 10:53:19"}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(item.status, SenderIdStatus::Active);
+    /// assert!(item.status.is_resolved());
+    /// assert_eq!(item.usecase, None);
+    /// ```
+    pub enum SenderIdStatus {
+        Pending = "pending" | "processing",
+        // Termii reports approved sender IDs as `unblock`.
+        Active = "active" | "approved" | "unblock" | "unblocked",
+        Blocked = "blocked" | "block",
+        Rejected = "rejected" | "declined",
+    }
+}
+
+impl SenderIdStatus {
+    /// Whether termii has finished reviewing the sender ID.
+    pub fn is_resolved(&self) -> bool {
+        !matches!(self, SenderIdStatus::Pending | SenderIdStatus::Unknown(_))
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SenderIDRequest {
     pub sender_id: String,
@@ -44,6 +142,21 @@ impl SenderIDRequest {
         }
     }
 
+    /// Check the request before sending it, termii rejects it otherwise.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_sender_id(&self.sender_id)?;
+
+        if self.usecase.trim().is_empty() {
+            return Err(ValidationError::MissingField("usecase"));
+        }
+
+        if self.company.trim().is_empty() {
+            return Err(ValidationError::MissingField("company"));
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn set_api_key(&mut self, api_key: &str) {
         self.api_key = Some(api_key.to_string());
     }
@@ -54,3 +167,57 @@ pub struct SenderIDRequestResponse {
     pub code: String,
     pub message: String,
 }
+
+/// The approved sender IDs of your account, refetched once they are older than the ttl.
+///
+/// Used by [`SenderID::ensure_approved`](crate::async_impl::rest::switch::SenderID::ensure_approved)
+/// so checking a sender before every send doesn't list all sender IDs every time.
+#[derive(Debug)]
+pub struct ApprovedSenderIds {
+    ttl: Duration,
+    state: Mutex<Option<(Instant, HashSet<String>)>>,
+}
+
+impl ApprovedSenderIds {
+    pub fn new(ttl: Duration) -> ApprovedSenderIds {
+        ApprovedSenderIds {
+            ttl,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Whether `sender_id` is approved, `None` when the list needs refetching.
+    pub fn contains(&self, sender_id: &str) -> Option<bool> {
+        match &*self.lock() {
+            Some((fetched_at, approved)) if fetched_at.elapsed() < self.ttl => {
+                Some(approved.contains(sender_id))
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace the list with the active sender IDs among `items`.
+    pub fn update<'i, I>(&self, items: I)
+    where
+        I: IntoIterator<Item = &'i SenderIDItem>,
+    {
+        let approved = items
+            .into_iter()
+            .filter(|item| item.status == SenderIdStatus::Active)
+            .map(|item| item.sender_id.clone())
+            .collect();
+
+        *self.lock() = Some((Instant::now(), approved));
+    }
+
+    /// Drop the list so the next check refetches it.
+    pub fn invalidate(&self) {
+        *self.lock() = None;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<(Instant, HashSet<String>)>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
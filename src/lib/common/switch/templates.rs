@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+use crate::common::{lenient, money::Money, switch::messaging::Media};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TemplatesData {
     pub product_name: String,
     pub otp: String,
@@ -17,41 +21,132 @@ impl TemplatesData {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct TemplatesRequest {
+/// Variables for templates that don't fit [`TemplatesData`], keyed by variable name.
+pub type TemplateVariables = BTreeMap<String, String>;
+
+/// A device template with a fixed set of variables.
+///
+/// Implement it on a struct holding the variables, or declare the struct with
+/// [`device_template!`](crate::device_template), and send it with
+/// [`TemplatesRequest::for_template`].
+pub trait DeviceTemplate {
+    const TEMPLATE_ID: &'static str;
+
+    fn variables(&self) -> TemplateVariables;
+}
+
+/// A device template message.
+///
+/// `data` holds the template variables, any serializable struct or map works.
+/// [`TemplatesData`] covers the common one time pin template.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::switch::{messaging::Media, templates::TemplatesRequest};
+///
+/// let payload = TemplatesRequest::with_variables(
+///     "+234XXXXXXXXXX".to_string(),
+///     "talert".to_string(),
+///     "1493-csdn3-ns34w-sd3434-dfdf".to_string(),
+/// )
+/// .variable("customer_name", "Ada")
+/// .variable("order_id", "1042")
+/// .media(Media {
+///     url: "https://example.com/receipt.pdf".to_string(),
+///     caption: "Your receipt".to_string(),
+/// });
+///
+/// let json = serde_json::to_value(&payload).unwrap();
+///
+/// assert_eq!(json["data"]["customer_name"], "Ada");
+/// assert_eq!(json["media"]["caption"], "Your receipt");
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TemplatesRequest<D = TemplatesData> {
     pub phone_number: String,
     pub device_id: String,
     pub template_id: String,
-    pub data: TemplatesData,
+    pub data: D,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<Media>,
     api_key: Option<String>,
 }
 
-impl TemplatesRequest {
+impl<D: Serialize> TemplatesRequest<D> {
     pub fn new(
         phone_number: String,
         device_id: String,
         template_id: String,
-        data: TemplatesData,
-    ) -> TemplatesRequest {
+        data: D,
+    ) -> TemplatesRequest<D> {
         TemplatesRequest {
             phone_number,
             device_id,
             template_id,
             data,
+            media: None,
             api_key: None,
         }
     }
 
+    /// Attach media, whatsapp templates with a header document or image need one.
+    pub fn media(mut self, media: Media) -> TemplatesRequest<D> {
+        self.media = Some(media);
+        self
+    }
+
     pub(crate) fn set_api_key(&mut self, api_key: &str) {
         self.api_key = Some(api_key.to_string());
     }
 }
 
+impl TemplatesRequest<TemplateVariables> {
+    /// A request for a declared [`DeviceTemplate`], its variables become the data.
+    pub fn for_template<T: DeviceTemplate + ?Sized>(
+        phone_number: String,
+        device_id: String,
+        template: &T,
+    ) -> TemplatesRequest<TemplateVariables> {
+        TemplatesRequest::new(
+            phone_number,
+            device_id,
+            T::TEMPLATE_ID.to_string(),
+            template.variables(),
+        )
+    }
+
+    /// A request whose variables are added one by one with [`variable`](Self::variable).
+    pub fn with_variables(
+        phone_number: String,
+        device_id: String,
+        template_id: String,
+    ) -> TemplatesRequest<TemplateVariables> {
+        TemplatesRequest::new(
+            phone_number,
+            device_id,
+            template_id,
+            TemplateVariables::new(),
+        )
+    }
+
+    pub fn variable(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> TemplatesRequest<TemplateVariables> {
+        self.data.insert(name.into(), value.into());
+        self
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TemplateItem {
     code: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     message_id: String,
     message: String,
-    balance: String,
+    /// The balance left, without a currency like the other send responses.
+    balance: Money,
     user: String,
 }
@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub enum InAppTokenMessageType {
-    NUMERIC,
-    ALPHANUMERIC,
+use crate::common::lenient;
+
+string_enum! {
+    pub enum InAppTokenMessageType {
+        NUMERIC = "NUMERIC",
+        ALPHANUMERIC = "ALPHANUMERIC",
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -47,8 +50,12 @@ pub struct InAppTokenResponse {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Data {
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub pin_id: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub otp: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub phone_number: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub phone_number_other: String,
 }
@@ -0,0 +1,296 @@
+//! Generate and verify in-app tokens locally.
+//!
+//! Codes are only ever kept as salted HMACs keyed with a secret held outside the store. Pins are
+//! short enough to brute force from a plain hash, so a leaked store only exposes them together
+//! with the secret.
+//! Tokens generated by termii's [`InAppToken`](crate::async_impl::rest::token::InAppToken)
+//! can be remembered with [`LocalOtp::remember`] and checked without another request.
+
+use std::{collections::HashMap, fmt, sync::Mutex, time::Duration};
+
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::common::{
+    errors::OtpError,
+    token::{
+        in_app_token::{InAppTokenMessageType, InAppTokenRequest, InAppTokenResponse},
+        otp_manager::{unix_now, OtpOutcome},
+    },
+};
+
+const NUMERIC: &[u8] = b"0123456789";
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Clone)]
+pub struct LocalOtpConfig {
+    pub pin_type: InAppTokenMessageType,
+    pub pin_length: u8,
+    pub pin_attempts: u8,
+    pub pin_time_to_live: Duration,
+}
+
+impl Default for LocalOtpConfig {
+    fn default() -> LocalOtpConfig {
+        LocalOtpConfig {
+            pin_type: InAppTokenMessageType::NUMERIC,
+            pin_length: 6,
+            pin_attempts: 3,
+            pin_time_to_live: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
+impl From<&InAppTokenRequest> for LocalOtpConfig {
+    fn from(request: &InAppTokenRequest) -> LocalOtpConfig {
+        LocalOtpConfig {
+            pin_type: request.pin_type.clone(),
+            pin_length: request.pin_length,
+            pin_attempts: request.pin_attempts,
+            pin_time_to_live: Duration::from_secs(request.pin_time_to_live as u64 * 60),
+        }
+    }
+}
+
+/// A hashed pin waiting to be verified.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LocalOtpEntry {
+    /// The termii pin id, when the pin came from an [`InAppTokenResponse`].
+    pub pin_id: Option<String>,
+    pub salt: String,
+    pub hash: String,
+    /// Unix timestamp (seconds) after which the pin is no longer valid.
+    pub expires_at: u64,
+    pub attempts: u8,
+    pub max_attempts: u8,
+}
+
+/// Storage for [`LocalOtpEntry`]s keyed by a caller supplied user key.
+pub trait LocalOtpStore {
+    fn get(&self, key: &str) -> Result<Option<LocalOtpEntry>, OtpError>;
+
+    fn put(&self, key: &str, entry: LocalOtpEntry) -> Result<(), OtpError>;
+
+    fn remove(&self, key: &str) -> Result<(), OtpError>;
+
+    /// Replace the entry under `key` with what `f` makes of it, removing it on `None`.
+    ///
+    /// Must be atomic, so parallel guesses each see the attempts counted by the others.
+    fn update(
+        &self,
+        key: &str,
+        f: &mut dyn FnMut(Option<LocalOtpEntry>) -> Option<LocalOtpEntry>,
+    ) -> Result<(), OtpError>;
+}
+
+/// A [`LocalOtpStore`] that keeps entries in process memory.
+#[derive(Debug, Default)]
+pub struct InMemoryLocalOtpStore {
+    entries: Mutex<HashMap<String, LocalOtpEntry>>,
+}
+
+impl InMemoryLocalOtpStore {
+    pub fn new() -> InMemoryLocalOtpStore {
+        InMemoryLocalOtpStore::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, LocalOtpEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl LocalOtpStore for InMemoryLocalOtpStore {
+    fn get(&self, key: &str) -> Result<Option<LocalOtpEntry>, OtpError> {
+        Ok(self.lock().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, entry: LocalOtpEntry) -> Result<(), OtpError> {
+        self.lock().insert(key.to_string(), entry);
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), OtpError> {
+        self.lock().remove(key);
+        Ok(())
+    }
+
+    fn update(
+        &self,
+        key: &str,
+        f: &mut dyn FnMut(Option<LocalOtpEntry>) -> Option<LocalOtpEntry>,
+    ) -> Result<(), OtpError> {
+        let mut entries = self.lock();
+
+        match f(entries.remove(key)) {
+            Some(entry) => entries.insert(key.to_string(), entry),
+            None => None,
+        };
+
+        Ok(())
+    }
+}
+
+pub struct LocalOtp<S: LocalOtpStore> {
+    store: S,
+    config: LocalOtpConfig,
+    secret: Vec<u8>,
+}
+
+impl<S: LocalOtpStore> LocalOtp<S> {
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::token::{
+    ///     local_otp::{InMemoryLocalOtpStore, LocalOtp, LocalOtpConfig},
+    ///     OtpOutcome,
+    /// };
+    ///
+    /// let otp = LocalOtp::new(
+    ///     InMemoryLocalOtpStore::new(),
+    ///     LocalOtpConfig::default(),
+    ///     "A secret kept out of the store",
+    /// );
+    ///
+    /// let pin = otp.generate("user-42").unwrap();
+    ///
+    /// assert_eq!(otp.verify("user-42", "000000x").unwrap(), OtpOutcome::WrongPin);
+    /// assert_eq!(otp.verify("user-42", &pin).unwrap(), OtpOutcome::Verified);
+    /// ```
+    pub fn new(store: S, config: LocalOtpConfig, secret: impl AsRef<[u8]>) -> LocalOtp<S> {
+        LocalOtp {
+            store,
+            config,
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Generate a pin for the user, replacing any pending one.
+    ///
+    /// The returned pin is not stored anywhere, deliver it to the user yourself.
+    /// Fails with [`OtpError::UnsupportedPinType`] when the configured pin type is not one this
+    /// version knows how to generate.
+    pub fn generate(&self, user_key: &str) -> Result<String, OtpError> {
+        let charset = match &self.config.pin_type {
+            InAppTokenMessageType::NUMERIC => NUMERIC,
+            InAppTokenMessageType::ALPHANUMERIC => ALPHANUMERIC,
+            InAppTokenMessageType::Unknown(pin_type) => {
+                return Err(OtpError::UnsupportedPinType(pin_type.to_string()))
+            }
+        };
+
+        let pin: String = (0..self.config.pin_length)
+            .map(|_| charset[OsRng.gen_range(0..charset.len())] as char)
+            .collect();
+
+        self.store_pin(user_key, &pin, None)?;
+
+        Ok(pin)
+    }
+
+    /// Remember a pin generated by termii so it can be verified without a round trip.
+    pub fn remember(&self, user_key: &str, response: &InAppTokenResponse) -> Result<(), OtpError> {
+        self.store_pin(
+            user_key,
+            &response.data.otp,
+            Some(response.data.pin_id.clone()),
+        )
+    }
+
+    /// The termii pin id of the user's pending pin, if it came from termii.
+    pub fn pin_id(&self, user_key: &str) -> Result<Option<String>, OtpError> {
+        Ok(self.store.get(user_key)?.and_then(|entry| entry.pin_id))
+    }
+
+    pub fn verify(&self, user_key: &str, pin: &str) -> Result<OtpOutcome, OtpError> {
+        let mut outcome = None;
+
+        self.store.update(user_key, &mut |entry| {
+            let mut entry = entry?;
+
+            if unix_now() >= entry.expires_at {
+                outcome = Some(OtpOutcome::Expired);
+                return None;
+            }
+
+            if entry.attempts >= entry.max_attempts {
+                outcome = Some(OtpOutcome::AttemptsExhausted);
+                return None;
+            }
+
+            let hash = self.hash_pin(&entry.salt, pin);
+
+            if constant_time_eq(hash.as_bytes(), entry.hash.as_bytes()) {
+                outcome = Some(OtpOutcome::Verified);
+                return None;
+            }
+
+            entry.attempts += 1;
+
+            if entry.attempts >= entry.max_attempts {
+                outcome = Some(OtpOutcome::AttemptsExhausted);
+                return None;
+            }
+
+            outcome = Some(OtpOutcome::WrongPin);
+            Some(entry)
+        })?;
+
+        outcome.ok_or_else(|| OtpError::NotIssued(user_key.to_string()))
+    }
+
+    fn hash_pin(&self, salt: &str, pin: &str) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC can take a key of any size.");
+        mac.update(salt.as_bytes());
+        mac.update(pin.as_bytes());
+        to_hex(&mac.finalize().into_bytes())
+    }
+
+    fn store_pin(&self, user_key: &str, pin: &str, pin_id: Option<String>) -> Result<(), OtpError> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let salt = to_hex(&salt);
+
+        self.store.put(
+            user_key,
+            LocalOtpEntry {
+                pin_id,
+                hash: self.hash_pin(&salt, pin),
+                salt,
+                expires_at: unix_now() + self.config.pin_time_to_live.as_secs(),
+                attempts: 0,
+                max_attempts: self.config.pin_attempts,
+            },
+        )
+    }
+}
+
+impl<S: LocalOtpStore + fmt::Debug> fmt::Debug for LocalOtp<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalOtp")
+            .field("store", &self.store)
+            .field("config", &self.config)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -6,3 +6,14 @@ pub use verify::*;
 
 pub mod in_app_token;
 pub use in_app_token::*;
+
+pub mod voice_token;
+pub use voice_token::*;
+
+pub mod otp_manager;
+pub use otp_manager::*;
+
+#[cfg(feature = "local-otp")]
+pub mod local_otp;
+#[cfg(feature = "local-otp")]
+pub use local_otp::*;
@@ -0,0 +1,295 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    errors::OtpError,
+    token::{request::RequestTokenChannel, verify::VerificationOutcome},
+};
+
+/// The result of checking a pin with the [`OtpManager`](crate::async_impl::rest::token::OtpManager).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtpOutcome {
+    Verified,
+    WrongPin,
+    Expired,
+    AttemptsExhausted,
+}
+
+impl From<VerificationOutcome> for OtpOutcome {
+    fn from(outcome: VerificationOutcome) -> OtpOutcome {
+        match outcome {
+            VerificationOutcome::Verified { .. } => OtpOutcome::Verified,
+            VerificationOutcome::Invalid => OtpOutcome::WrongPin,
+            VerificationOutcome::AttemptsExceeded => OtpOutcome::AttemptsExhausted,
+            // Termii forgets pins once they expire.
+            VerificationOutcome::Expired | VerificationOutcome::NotFound => OtpOutcome::Expired,
+        }
+    }
+}
+
+/// How a token reaches the user.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtpDelivery {
+    /// A message on one of termii's token channels.
+    Message(RequestTokenChannel),
+    /// A call reading out the pin, for numbers the messages don't reach.
+    Voice,
+}
+
+impl From<RequestTokenChannel> for OtpDelivery {
+    fn from(channel: RequestTokenChannel) -> OtpDelivery {
+        OtpDelivery::Message(channel)
+    }
+}
+
+/// Settings shared by the async and blocking otp managers.
+#[derive(Debug, Clone)]
+pub struct OtpConfig {
+    /// Minimum time between two tokens sent to the same user.
+    pub resend_cooldown: Duration,
+    /// Maximum number of resends allowed for a single issued token.
+    pub max_resends: u8,
+    /// Deliveries tried in order on every resend, after the channel of the request template,
+    /// such as [`OtpDelivery::Voice`] when the messages don't arrive.
+    pub fallback_channels: Vec<OtpDelivery>,
+}
+
+impl OtpConfig {
+    /// How the token is delivered after `resends` resends, starting from `template_channel`.
+    ///
+    /// Each resend moves to the next of the `fallback_channels`, staying on the last one once
+    /// they run out.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::token::{OtpConfig, OtpDelivery, RequestTokenChannel};
+    ///
+    /// let config = OtpConfig {
+    ///     fallback_channels: vec![RequestTokenChannel::Whatsapp.into(), OtpDelivery::Voice],
+    ///     ..OtpConfig::default()
+    /// };
+    ///
+    /// let template_channel = RequestTokenChannel::Dnd;
+    ///
+    /// assert_eq!(
+    ///     config.delivery_for(&template_channel, 0),
+    ///     OtpDelivery::Message(RequestTokenChannel::Dnd)
+    /// );
+    /// assert_eq!(
+    ///     config.delivery_for(&template_channel, 1),
+    ///     OtpDelivery::Message(RequestTokenChannel::Whatsapp)
+    /// );
+    /// assert_eq!(config.delivery_for(&template_channel, 2), OtpDelivery::Voice);
+    /// assert_eq!(config.delivery_for(&template_channel, 3), OtpDelivery::Voice);
+    /// ```
+    pub fn delivery_for(&self, template_channel: &RequestTokenChannel, resends: u8) -> OtpDelivery {
+        let template_delivery = || OtpDelivery::Message(template_channel.clone());
+
+        match resends {
+            0 => template_delivery(),
+            n => self
+                .fallback_channels
+                .get(n as usize - 1)
+                .or_else(|| self.fallback_channels.last())
+                .cloned()
+                .unwrap_or_else(template_delivery),
+        }
+    }
+}
+
+impl Default for OtpConfig {
+    fn default() -> OtpConfig {
+        OtpConfig {
+            resend_cooldown: Duration::from_secs(60),
+            max_resends: 3,
+            fallback_channels: Vec::new(),
+        }
+    }
+}
+
+/// The state kept for a user between issuing and verifying a token.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtpSession {
+    pub pin_id: String,
+    pub phone_number: String,
+    pub channel: OtpDelivery,
+    /// Unix timestamp (seconds) of the last token sent.
+    pub sent_at: u64,
+    /// Unix timestamp (seconds) after which the pin is no longer valid.
+    pub expires_at: u64,
+    pub attempts: u8,
+    pub max_attempts: u8,
+    pub resends: u8,
+}
+
+impl OtpSession {
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn attempts_exhausted(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+
+    /// Seconds left before another token can be sent, if any.
+    pub fn cooldown_remaining(&self, now: u64, cooldown: Duration) -> Option<u64> {
+        let ready_at = self.sent_at + cooldown.as_secs();
+
+        if now < ready_at {
+            Some(ready_at - now)
+        } else {
+            None
+        }
+    }
+}
+
+/// Storage for [`OtpSession`]s keyed by a caller supplied user key.
+///
+/// Implement this to keep sessions in a shared store like redis or a database.
+pub trait OtpStore {
+    fn get(&self, key: &str) -> Result<Option<OtpSession>, OtpError>;
+
+    fn put(&self, key: &str, session: OtpSession) -> Result<(), OtpError>;
+
+    fn remove(&self, key: &str) -> Result<(), OtpError>;
+
+    /// Replace the session under `key` with what `f` makes of it, removing it on `None`.
+    ///
+    /// Must be atomic, so parallel guesses each see the attempts counted by the others.
+    fn update(
+        &self,
+        key: &str,
+        f: &mut dyn FnMut(Option<OtpSession>) -> Option<OtpSession>,
+    ) -> Result<(), OtpError>;
+}
+
+/// An [`OtpStore`] that keeps sessions in process memory.
+#[derive(Debug, Default)]
+pub struct InMemoryOtpStore {
+    sessions: Mutex<HashMap<String, OtpSession>>,
+}
+
+impl InMemoryOtpStore {
+    pub fn new() -> InMemoryOtpStore {
+        InMemoryOtpStore::default()
+    }
+
+    /// Drop every session whose pin has expired.
+    pub fn purge_expired(&self) {
+        let now = unix_now();
+        self.lock().retain(|_, session| !session.is_expired(now));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, OtpSession>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl OtpStore for InMemoryOtpStore {
+    fn get(&self, key: &str) -> Result<Option<OtpSession>, OtpError> {
+        Ok(self.lock().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, session: OtpSession) -> Result<(), OtpError> {
+        self.lock().insert(key.to_string(), session);
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), OtpError> {
+        self.lock().remove(key);
+        Ok(())
+    }
+
+    fn update(
+        &self,
+        key: &str,
+        f: &mut dyn FnMut(Option<OtpSession>) -> Option<OtpSession>,
+    ) -> Result<(), OtpError> {
+        let mut sessions = self.lock();
+
+        match f(sessions.remove(key)) {
+            Some(session) => sessions.insert(key.to_string(), session),
+            None => None,
+        };
+
+        Ok(())
+    }
+}
+
+/// A verify attempt, counted against the session before the pin is sent to termii.
+pub(crate) enum Attempt {
+    Counted(OtpSession),
+    Refused(OtpOutcome),
+}
+
+/// Count an attempt against the user's session, refusing it when the pin expired or no attempt
+/// is left.
+pub(crate) fn begin_attempt<S>(store: &S, user_key: &str) -> Result<Attempt, OtpError>
+where
+    S: OtpStore + ?Sized,
+{
+    let now = unix_now();
+    let mut attempt = None;
+
+    store.update(user_key, &mut |session| {
+        let mut session = session?;
+
+        if session.is_expired(now) {
+            attempt = Some(Attempt::Refused(OtpOutcome::Expired));
+            return None;
+        }
+
+        if session.attempts_exhausted() {
+            attempt = Some(Attempt::Refused(OtpOutcome::AttemptsExhausted));
+            return None;
+        }
+
+        session.attempts += 1;
+        attempt = Some(Attempt::Counted(session.clone()));
+        Some(session)
+    })?;
+
+    attempt.ok_or_else(|| OtpError::NotIssued(user_key.to_string()))
+}
+
+/// Settle the session after termii checked the pin of a counted attempt.
+///
+/// The session is only removed while it still holds the same pin, so a token resent in the
+/// meantime is kept.
+pub(crate) fn finish_attempt<S>(
+    store: &S,
+    user_key: &str,
+    session: &OtpSession,
+    outcome: OtpOutcome,
+) -> Result<OtpOutcome, OtpError>
+where
+    S: OtpStore + ?Sized,
+{
+    let outcome = match outcome {
+        OtpOutcome::WrongPin if !session.attempts_exhausted() => return Ok(outcome),
+        OtpOutcome::WrongPin => OtpOutcome::AttemptsExhausted,
+        outcome => outcome,
+    };
+
+    store.update(user_key, &mut |current| {
+        current.filter(|current| current.pin_id != session.pin_id)
+    })?;
+
+    Ok(outcome)
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
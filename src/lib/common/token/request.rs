@@ -1,28 +1,40 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub enum RequestTokenMessageType {
-    NUMERIC,
-    ALPHANUMERIC,
+use crate::common::{errors::ValidationError, lenient, switch::sender_id::validate_sender};
+
+string_enum! {
+    pub enum RequestTokenMessageType {
+        NUMERIC = "NUMERIC",
+        ALPHANUMERIC = "ALPHANUMERIC",
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub enum RequestTokenPinType {
-    NUMERIC,
-    ALPHANUMERIC,
+string_enum! {
+    pub enum RequestTokenPinType {
+        NUMERIC = "NUMERIC",
+        ALPHANUMERIC = "ALPHANUMERIC",
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub enum RequestTokenChannel {
-    #[serde(rename = "generic")]
-    Generic,
-    #[serde(rename = "dnd")]
-    Dnd,
-    #[serde(rename = "whatsapp")]
-    Whatsapp,
+string_enum! {
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::token::RequestTokenChannel;
+    ///
+    /// let channel: RequestTokenChannel = serde_json::from_str(r#""email""#).unwrap();
+    ///
+    /// assert_eq!(channel, RequestTokenChannel::Unknown("email".to_string()));
+    /// assert_eq!(serde_json::to_string(&channel).unwrap(), r#""email""#);
+    /// ```
+    pub enum RequestTokenChannel {
+        Generic = "generic",
+        Dnd = "dnd",
+        Whatsapp = "whatsapp",
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RequestTokenRequest {
     pub message_type: RequestTokenMessageType,
     pub to: String,
@@ -65,15 +77,204 @@ impl RequestTokenRequest {
         }
     }
 
+    /// Start building a request with termii's recommended defaults.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::token::{RequestTokenChannel, RequestTokenRequest};
+    ///
+    /// let otp_request = RequestTokenRequest::builder()
+    ///     .to("234XXXXXXXXXX")
+    ///     .from("YourOrg")
+    ///     .channel(RequestTokenChannel::Dnd)
+    ///     .pin_length(6)
+    ///     .message_text("Your pin is < 1234 >")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(otp_request.pin_placeholder, "< 1234 >");
+    /// ```
+    pub fn builder() -> RequestTokenRequestBuilder {
+        RequestTokenRequestBuilder::default()
+    }
+
     pub(crate) fn set_api_key(&mut self, api_key: &str) {
         self.api_key = Some(api_key.to_string());
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Builds a [`RequestTokenRequest`], checking it against termii's limits.
+#[derive(Debug, Clone)]
+pub struct RequestTokenRequestBuilder {
+    message_type: RequestTokenMessageType,
+    to: Option<String>,
+    from: Option<String>,
+    channel: RequestTokenChannel,
+    pin_attempts: u8,
+    pin_time_to_live: usize,
+    pin_length: u8,
+    pin_placeholder: String,
+    message_text: Option<String>,
+    pin_type: RequestTokenPinType,
+}
+
+impl Default for RequestTokenRequestBuilder {
+    fn default() -> RequestTokenRequestBuilder {
+        RequestTokenRequestBuilder {
+            message_type: RequestTokenMessageType::NUMERIC,
+            to: None,
+            from: None,
+            channel: RequestTokenChannel::Generic,
+            pin_attempts: 3,
+            pin_time_to_live: 10,
+            pin_length: 6,
+            pin_placeholder: String::from("< 1234 >"),
+            message_text: None,
+            pin_type: RequestTokenPinType::NUMERIC,
+        }
+    }
+}
+
+impl RequestTokenRequestBuilder {
+    pub const PIN_LENGTH: (u8, u8) = (4, 8);
+    pub const PIN_ATTEMPTS: (u8, u8) = (1, 10);
+    /// Minutes a pin stays valid.
+    pub const PIN_TIME_TO_LIVE: (usize, usize) = (0, 60);
+
+    pub fn message_type(mut self, message_type: RequestTokenMessageType) -> Self {
+        self.message_type = message_type;
+        self
+    }
+
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    pub fn channel(mut self, channel: RequestTokenChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    pub fn pin_attempts(mut self, pin_attempts: u8) -> Self {
+        self.pin_attempts = pin_attempts;
+        self
+    }
+
+    pub fn pin_time_to_live(mut self, minutes: usize) -> Self {
+        self.pin_time_to_live = minutes;
+        self
+    }
+
+    pub fn pin_length(mut self, pin_length: u8) -> Self {
+        self.pin_length = pin_length;
+        self
+    }
+
+    pub fn pin_placeholder(mut self, pin_placeholder: impl Into<String>) -> Self {
+        self.pin_placeholder = pin_placeholder.into();
+        self
+    }
+
+    pub fn message_text(mut self, message_text: impl Into<String>) -> Self {
+        self.message_text = Some(message_text.into());
+        self
+    }
+
+    pub fn pin_type(mut self, pin_type: RequestTokenPinType) -> Self {
+        self.pin_type = pin_type;
+        self
+    }
+
+    pub fn build(self) -> Result<RequestTokenRequest, ValidationError> {
+        let to = required("to", self.to)?;
+        let from = required("from", self.from)?;
+
+        if self.channel != RequestTokenChannel::Whatsapp {
+            validate_sender("from", &from)?;
+        }
+        let message_text = required("message_text", self.message_text)?;
+
+        in_range(
+            "pin_length",
+            self.pin_length as usize,
+            Self::PIN_LENGTH.0 as usize,
+            Self::PIN_LENGTH.1 as usize,
+        )?;
+        in_range(
+            "pin_attempts",
+            self.pin_attempts as usize,
+            Self::PIN_ATTEMPTS.0 as usize,
+            Self::PIN_ATTEMPTS.1 as usize,
+        )?;
+        in_range(
+            "pin_time_to_live",
+            self.pin_time_to_live,
+            Self::PIN_TIME_TO_LIVE.0,
+            Self::PIN_TIME_TO_LIVE.1,
+        )?;
+
+        if self.pin_placeholder.is_empty() {
+            return Err(ValidationError::MissingField("pin_placeholder"));
+        }
+
+        if !message_text.contains(&self.pin_placeholder) {
+            return Err(ValidationError::PlaceholderNotInMessage {
+                placeholder: self.pin_placeholder,
+            });
+        }
+
+        Ok(RequestTokenRequest::new(
+            self.message_type,
+            to,
+            from,
+            self.channel,
+            self.pin_attempts,
+            self.pin_time_to_live,
+            self.pin_length,
+            self.pin_placeholder,
+            message_text,
+            self.pin_type,
+        ))
+    }
+}
+
+fn required(field: &'static str, value: Option<String>) -> Result<String, ValidationError> {
+    match value {
+        Some(value) if !value.trim().is_empty() => Ok(value),
+        _ => Err(ValidationError::MissingField(field)),
+    }
+}
+
+fn in_range(
+    field: &'static str,
+    value: usize,
+    min: usize,
+    max: usize,
+) -> Result<(), ValidationError> {
+    if value < min || value > max {
+        return Err(ValidationError::OutOfRange {
+            field,
+            min,
+            max,
+            value,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestTokenResponse {
-    #[serde(rename = "pinId")]
+    #[serde(rename = "pinId", deserialize_with = "lenient::string_or_number")]
     pub pin_id: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
     pub to: String,
     #[serde(rename = "smsStatus")]
     pub sms_status: String,
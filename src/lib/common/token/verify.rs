@@ -28,3 +28,113 @@ pub struct VerifyTokenResponse {
     pub verified: bool,
     pub msisdn: String,
 }
+
+/// What Termii made of a pin sent for verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    Verified { pin_id: String, msisdn: String },
+    Invalid,
+    Expired,
+    AttemptsExceeded,
+    NotFound,
+}
+
+impl VerificationOutcome {
+    /// Read the outcome from the status code and body of a verify response.
+    ///
+    /// Termii only answers with a [`VerifyTokenResponse`] for a correct or wrong pin, expired,
+    /// used up and unknown pins come back with a `verified` string or an error `message` instead.
+    /// Returns `None` when the response says nothing about the pin, such as a rejected API key,
+    /// so it is not mistaken for a wrong pin.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::token::VerificationOutcome;
+    ///
+    /// let outcome = VerificationOutcome::from_response_text(
+    ///     200,
+    ///     r#"{"pinId": "c8dcd048-5e7f-4347-8c89-4470c3af0b", "verified": "Expired"}"#,
+    /// );
+    /// assert_eq!(outcome, Some(VerificationOutcome::Expired));
+    ///
+    /// let outcome = VerificationOutcome::from_response_text(400, r#"{"message": "Invalid pin"}"#);
+    /// assert_eq!(outcome, Some(VerificationOutcome::Invalid));
+    ///
+    /// let outcome = VerificationOutcome::from_response_text(
+    ///     401,
+    ///     r#"{"code": "401", "message": "Invalid API key"}"#,
+    /// );
+    /// assert_eq!(outcome, None);
+    /// ```
+    pub fn from_response_text(status_code: u16, text: &str) -> Option<VerificationOutcome> {
+        if !(200..300).contains(&status_code) && status_code != 400 && status_code != 404 {
+            return None;
+        }
+
+        let body: serde_json::Value = serde_json::from_str(text).ok()?;
+
+        let field = |name: &str| match body.get(name) {
+            Some(serde_json::Value::String(value)) => value.to_string(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(value) => value.to_string(),
+        };
+
+        let verified = || VerificationOutcome::Verified {
+            pin_id: field("pinId"),
+            msisdn: field("msisdn"),
+        };
+
+        match body.get("verified") {
+            Some(serde_json::Value::Bool(true)) => return Some(verified()),
+            Some(serde_json::Value::Bool(false)) => return Some(VerificationOutcome::Invalid),
+            Some(serde_json::Value::String(value)) => match value.to_lowercase().as_str() {
+                "true" => return Some(verified()),
+                "false" => return Some(VerificationOutcome::Invalid),
+                _ => {
+                    if let Some(outcome) = VerificationOutcome::from_message(value) {
+                        return Some(outcome);
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        let message = field("message");
+
+        if !is_about_pin(&message) {
+            return None;
+        }
+
+        VerificationOutcome::from_message(&message)
+    }
+
+    pub fn is_verified(&self) -> bool {
+        matches!(self, VerificationOutcome::Verified { .. })
+    }
+
+    fn from_message(message: &str) -> Option<VerificationOutcome> {
+        let message = message.to_lowercase();
+
+        if message.contains("expired") {
+            Some(VerificationOutcome::Expired)
+        } else if message.contains("attempt") {
+            Some(VerificationOutcome::AttemptsExceeded)
+        } else if message.contains("not found") || message.contains("does not exist") {
+            Some(VerificationOutcome::NotFound)
+        } else if message.contains("invalid")
+            || message.contains("incorrect")
+            || message.contains("wrong")
+        {
+            Some(VerificationOutcome::Invalid)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether an error message is about the pin rather than the request, such as an invalid API key.
+fn is_about_pin(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("pin") || message.contains("otp")
+}
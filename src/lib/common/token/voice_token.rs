@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::lenient;
+
+/// A pin read out to the user in a voice call, verified like any other token.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VoiceTokenRequest {
+    pub phone_number: String,
+    pub pin_attempts: u8,
+    pub pin_time_to_live: usize,
+    pub pin_length: u8,
+    api_key: Option<String>,
+}
+
+impl VoiceTokenRequest {
+    pub fn new(
+        phone_number: String,
+        pin_attempts: u8,
+        pin_time_to_live: usize,
+        pin_length: u8,
+    ) -> VoiceTokenRequest {
+        VoiceTokenRequest {
+            phone_number,
+            pin_attempts,
+            pin_time_to_live,
+            pin_length,
+            api_key: None,
+        }
+    }
+
+    pub(crate) fn set_api_key(&mut self, api_key: &str) {
+        self.api_key = Some(api_key.to_string());
+    }
+}
+
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::token::VoiceTokenResponse;
+///
+/// let response: VoiceTokenResponse = serde_json::from_str(
+///     r#"{
+///     "code": "ok",
+///     "message": "Successfully Sent",
+///     "pinId": "29ae67c2-c8e1-4165-8a51-8d3d7c298081",
+///     "phone_number": 2348109077743,
+///     "phone_number_other": ""
+/// }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(response.pin_id, "29ae67c2-c8e1-4165-8a51-8d3d7c298081");
+/// assert_eq!(response.phone_number, "2348109077743");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceTokenResponse {
+    pub code: String,
+    pub message: String,
+    #[serde(rename = "pinId", deserialize_with = "lenient::string_or_number")]
+    pub pin_id: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub phone_number: String,
+    #[serde(default, deserialize_with = "lenient::optional_string_or_number")]
+    pub phone_number_other: Option<String>,
+}
@@ -0,0 +1,77 @@
+use std::{future::Future, pin::Pin};
+
+use actix_web::{
+    dev::Payload,
+    http::StatusCode,
+    web::{Bytes, Data},
+    FromRequest, HttpRequest, ResponseError,
+};
+
+use crate::common::{
+    errors::WebhookError,
+    webhooks::{VerifiedWebhookEvent, WebhookEvent, WebhookVerifier, SIGNATURE_HEADER},
+};
+
+/// Take a [`WebhookEvent`] as a handler argument to parse the request body.
+impl FromRequest for WebhookEvent {
+    type Error = WebhookError;
+    type Future = Pin<Box<dyn Future<Output = Result<WebhookEvent, WebhookError>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let body = Bytes::from_request(req, payload);
+
+        Box::pin(async move {
+            let body = body
+                .await
+                .map_err(|err| WebhookError::Body(err.to_string()))?;
+
+            WebhookEvent::from_slice(&body)
+        })
+    }
+}
+
+/// Take a [`VerifiedWebhookEvent`] as a handler argument to check the signature with the
+/// [`WebhookVerifier`] in the app data, then parse the request body.
+impl FromRequest for VerifiedWebhookEvent {
+    type Error = WebhookError;
+    type Future = Pin<Box<dyn Future<Output = Result<VerifiedWebhookEvent, WebhookError>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let verifier = req
+            .app_data::<Data<WebhookVerifier>>()
+            .map(|verifier| verifier.get_ref().clone())
+            .or_else(|| req.app_data::<WebhookVerifier>().cloned());
+
+        let signature = req
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let body = Bytes::from_request(req, payload);
+
+        Box::pin(async move {
+            let verifier = verifier.ok_or(WebhookError::MissingVerifier)?;
+
+            let body = body
+                .await
+                .map_err(|err| WebhookError::Body(err.to_string()))?;
+
+            verifier
+                .parse(signature.as_deref(), &body)
+                .map(VerifiedWebhookEvent)
+        })
+    }
+}
+
+impl ResponseError for WebhookError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            WebhookError::MissingSignature | WebhookError::InvalidSignature => {
+                StatusCode::UNAUTHORIZED
+            }
+            WebhookError::MissingVerifier => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
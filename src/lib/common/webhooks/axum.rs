@@ -0,0 +1,72 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRef, FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::common::{
+    errors::WebhookError,
+    webhooks::{VerifiedWebhookEvent, WebhookEvent, WebhookVerifier, SIGNATURE_HEADER},
+};
+
+/// Take a [`WebhookEvent`] as a handler argument to parse the request body.
+#[async_trait]
+impl<S> FromRequest<S> for WebhookEvent
+where
+    S: Send + Sync,
+{
+    type Rejection = WebhookError;
+
+    async fn from_request(req: Request, state: &S) -> Result<WebhookEvent, WebhookError> {
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|err| WebhookError::Body(err.body_text()))?;
+
+        WebhookEvent::from_slice(&body)
+    }
+}
+
+/// Take a [`VerifiedWebhookEvent`] as a handler argument to check the signature with the
+/// [`WebhookVerifier`] in the router state, then parse the request body.
+#[async_trait]
+impl<S> FromRequest<S> for VerifiedWebhookEvent
+where
+    S: Send + Sync,
+    WebhookVerifier: FromRef<S>,
+{
+    type Rejection = WebhookError;
+
+    async fn from_request(req: Request, state: &S) -> Result<VerifiedWebhookEvent, WebhookError> {
+        let verifier = WebhookVerifier::from_ref(state);
+
+        let signature = req
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|err| WebhookError::Body(err.body_text()))?;
+
+        verifier
+            .parse(signature.as_deref(), &body)
+            .map(VerifiedWebhookEvent)
+    }
+}
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            WebhookError::MissingSignature | WebhookError::InvalidSignature => {
+                StatusCode::UNAUTHORIZED
+            }
+            WebhookError::MissingVerifier => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        };
+
+        (status, self.to_string()).into_response()
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    errors::WebhookError,
+    insights::history::DeliveryStatus,
+    lenient,
+    money::{self, Money},
+};
+
+/// A callback sent by termii to your notify URL.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebhookEvent {
+    Outbound(DeliveryReport),
+    Inbound(InboundMessage),
+}
+
+impl WebhookEvent {
+    /// Parse a webhook from the raw request body.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use termii_rust::common::{insights::DeliveryStatus, webhooks::WebhookEvent};
+    ///
+    /// let body = br#"{
+    ///     "type": "outbound",
+    ///     "id": "3017544054459309654184010",
+    ///     "receiver": "2347062668208",
+    ///     "message": "Your pin is 123456",
+    ///     "sent_at": "2022-08-09 10:22:37",
+    ///     "cost": "2.00",
+    ///     "status": "DELIVERED",
+    ///     "channel": "generic"
+    /// }"#;
+    ///
+    /// match WebhookEvent::from_slice(body).unwrap() {
+    ///     WebhookEvent::Outbound(report) => {
+    ///         assert_eq!(report.delivery_status(), DeliveryStatus::Delivered)
+    ///     }
+    ///     WebhookEvent::Inbound(message) => println!("{:?}", message),
+    /// }
+    /// ```
+    pub fn from_slice(body: &[u8]) -> Result<WebhookEvent, WebhookError> {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
+
+/// The delivery report of a message you sent.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeliveryReport {
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub id: String,
+    #[serde(default, deserialize_with = "lenient::optional_string_or_number")]
+    pub message_id: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub sender: Option<String>,
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub receiver: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub message: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub sent_at: Option<String>,
+    #[serde(default, deserialize_with = "money::optional_money")]
+    pub cost: Option<Money>,
+    pub status: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub channel: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub notify_url: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub notify_id: Option<String>,
+}
+
+impl DeliveryReport {
+    pub fn delivery_status(&self) -> DeliveryStatus {
+        DeliveryStatus::from(self.status.as_str())
+    }
+}
+
+/// A message sent to one of your numbers or devices.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InboundMessage {
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub id: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub sender: String,
+    #[serde(deserialize_with = "lenient::string_or_number")]
+    pub receiver: String,
+    pub message: String,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub sent_at: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub channel: Option<String>,
+    #[serde(default, deserialize_with = "lenient::empty_string_as_none")]
+    pub media_url: Option<String>,
+}
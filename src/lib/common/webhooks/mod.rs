@@ -0,0 +1,15 @@
+//! Types for the delivery reports and inbound messages termii posts to your notify URL.
+//!
+//! Framework extractors are available for axum and actix-web behind the `axum` and `actix` features.
+
+pub mod events;
+pub use events::*;
+
+pub mod signature;
+pub use signature::*;
+
+#[cfg(feature = "axum")]
+mod axum;
+
+#[cfg(feature = "actix")]
+mod actix;
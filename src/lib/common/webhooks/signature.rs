@@ -0,0 +1,93 @@
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+use crate::common::{errors::WebhookError, webhooks::WebhookEvent};
+
+/// The header termii puts the body signature in.
+pub const SIGNATURE_HEADER: &str = "X-Termii-Signature";
+
+/// Checks that a webhook was signed by termii with your secret key.
+///
+/// Termii signs the raw request body with HMAC-SHA512 and sends the hex digest in the
+/// [`SIGNATURE_HEADER`] header.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::common::{errors::WebhookError, webhooks::WebhookVerifier};
+///
+/// let verifier = WebhookVerifier::new("Your secret key");
+///
+/// let body = br#"{"type": "inbound", "id": "1", "sender": "2347062668208", "receiver": "Termii", "message": "Hi"}"#;
+///
+/// let result = verifier.parse(Some("not-the-signature"), body);
+///
+/// assert!(matches!(result, Err(WebhookError::InvalidSignature)));
+/// ```
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    secret: Vec<u8>,
+}
+
+impl WebhookVerifier {
+    pub fn new(secret: impl AsRef<[u8]>) -> WebhookVerifier {
+        WebhookVerifier {
+            secret: secret.as_ref().to_vec(),
+        }
+    }
+
+    /// Check the signature header value against the raw body in constant time.
+    pub fn verify(&self, signature: Option<&str>, body: &[u8]) -> Result<(), WebhookError> {
+        let signature = signature
+            .map(str::trim)
+            .filter(|signature| !signature.is_empty())
+            .ok_or(WebhookError::MissingSignature)?;
+
+        let signature = decode_hex(signature).ok_or(WebhookError::InvalidSignature)?;
+
+        let mut mac =
+            Hmac::<Sha512>::new_from_slice(&self.secret).expect("HMAC can take a key of any size.");
+        mac.update(body);
+
+        mac.verify_slice(&signature)
+            .map_err(|_| WebhookError::InvalidSignature)
+    }
+
+    /// Verify the signature then parse the body.
+    pub fn parse(
+        &self,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<WebhookEvent, WebhookError> {
+        self.verify(signature, body)?;
+        WebhookEvent::from_slice(body)
+    }
+}
+
+impl fmt::Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// A [`WebhookEvent`] whose signature has been checked by a [`WebhookVerifier`].
+///
+/// Use it as a handler argument with the `axum` or `actix` features, the verifier is taken
+/// from the router state or the app data.
+#[derive(Debug, Clone)]
+pub struct VerifiedWebhookEvent(pub WebhookEvent);
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 == 1 {
+        return None;
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
@@ -43,3 +43,129 @@ macro_rules! response_or_error_text_async {
         }
     }};
 }
+
+// Declares an enum of the string values termii sends, with an `Unknown` variant that keeps values
+// this version does not know about so they serialize back unchanged. The first literal of a
+// variant is what it serializes to, the others are aliases accepted when parsing. Parsing ignores
+// case and surrounding whitespace.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $value:literal $(| $alias:literal)*
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+        #[serde(from = "String", into = "String")]
+        #[non_exhaustive]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+            /// A value this version does not know about, kept as sent.
+            Unknown(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> $name {
+                let trimmed = value.trim();
+                $(
+                    if trimmed.eq_ignore_ascii_case($value)
+                        $(|| trimmed.eq_ignore_ascii_case($alias))*
+                    {
+                        return $name::$variant;
+                    }
+                )*
+                $name::Unknown(value)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                match value {
+                    $name::Unknown(value) => value,
+                    known => known.as_str().to_string(),
+                }
+            }
+        }
+    };
+}
+
+/// Declare a device template as a struct of its variables.
+///
+/// The struct implements [`DeviceTemplate`](crate::common::switch::templates::DeviceTemplate) so
+/// it can be sent with [`Templates::send_template`](crate::async_impl::rest::switch::Templates::send_template).
+/// Field names are the template variable names and every field type must implement `Display`,
+/// so a misspelled or missing variable fails to compile instead of being rejected by termii.
+///
+/// ## Examples
+///
+/// ```rust
+/// use termii_rust::{common::switch::templates::DeviceTemplate, device_template};
+///
+/// device_template! {
+///     /// Sent when an order leaves the warehouse.
+///     pub struct OrderShipped = "1493-csdn3-ns34w-sd3434-dfdf" {
+///         pub customer_name: String,
+///         pub order_id: u64,
+///     }
+/// }
+///
+/// let template = OrderShipped {
+///     customer_name: "Ada".to_string(),
+///     order_id: 1042,
+/// };
+///
+/// assert_eq!(OrderShipped::TEMPLATE_ID, "1493-csdn3-ns34w-sd3434-dfdf");
+/// assert_eq!(template.variables()["order_id"], "1042");
+/// ```
+#[macro_export]
+macro_rules! device_template {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $template_id:literal {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $field:ident : $field_type:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis $field: $field_type,
+            )*
+        }
+
+        impl $crate::common::switch::templates::DeviceTemplate for $name {
+            const TEMPLATE_ID: &'static str = $template_id;
+
+            fn variables(&self) -> $crate::common::switch::templates::TemplateVariables {
+                let mut variables = $crate::common::switch::templates::TemplateVariables::new();
+                $(
+                    variables.insert(
+                        ::std::string::String::from(stringify!($field)),
+                        ::std::string::ToString::to_string(&self.$field),
+                    );
+                )*
+                variables
+            }
+        }
+    };
+}
@@ -85,6 +85,15 @@
 //! ## Optional Features
 //!
 //! The crate provides an optional [`blocking`][blocking] module which provides a blocking interface to the Termii API.
+//!
+//! The `local-otp` feature enables the [`local_otp`](common::token::local_otp) module which generates and verifies
+//! in-app tokens locally without a round trip to Termii.
+//!
+//! The `axum` and `actix` features let [`WebhookEvent`](common::webhooks::WebhookEvent) be used directly as a handler
+//! argument in those frameworks to receive Termii's delivery reports and inbound messages.
+//!
+//! The `outbox-sqlite` feature adds a SQLite backed [`OutboxStore`](common::outbox::OutboxStore) so queued sends
+//! survive restarts.
 
 #[macro_use]
 pub mod macros;
@@ -0,0 +1,177 @@
+//! The otp manager's cooldown, resend and attempt rules, checked without reaching termii.
+
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use termii_rust::{
+    async_impl::rest::{termii::Termii, token::OtpManager},
+    common::{
+        errors::OtpError,
+        token::{
+            InMemoryOtpStore, OtpConfig, OtpDelivery, OtpOutcome, OtpSession, OtpStore,
+            RequestTokenChannel, RequestTokenMessageType, RequestTokenPinType, RequestTokenRequest,
+        },
+    },
+};
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn template() -> RequestTokenRequest {
+    RequestTokenRequest::new(
+        RequestTokenMessageType::NUMERIC,
+        String::new(),
+        String::from("YourOrg"),
+        RequestTokenChannel::Generic,
+        3,
+        10,
+        6,
+        String::from("< 1234 >"),
+        String::from("Your pin is < 1234 >"),
+        RequestTokenPinType::NUMERIC,
+    )
+}
+
+fn session(sent_at: u64, attempts: u8, resends: u8) -> OtpSession {
+    OtpSession {
+        pin_id: "29ae67c2-c8e1-4165-8a51-8d3d7c298081".to_string(),
+        phone_number: "2348109077743".to_string(),
+        channel: OtpDelivery::Message(RequestTokenChannel::Generic),
+        sent_at,
+        expires_at: now() + 10 * 60,
+        attempts,
+        max_attempts: 3,
+        resends,
+    }
+}
+
+fn store_with(session: OtpSession) -> InMemoryOtpStore {
+    let store = InMemoryOtpStore::new();
+    store.put("user-42", session).unwrap();
+    store
+}
+
+#[tokio::test]
+async fn issue_and_resend_wait_for_the_cooldown() {
+    let client = Termii::new("Your API key");
+    let otp = OtpManager::new(&client.token, template(), store_with(session(now(), 0, 0)));
+
+    assert!(matches!(
+        otp.issue("user-42", "2348109077743").await,
+        Err(OtpError::CooldownActive { .. })
+    ));
+    assert!(matches!(
+        otp.resend("user-42").await,
+        Err(OtpError::CooldownActive { .. })
+    ));
+}
+
+#[tokio::test]
+async fn issue_and_resend_stop_at_the_resend_limit() {
+    let client = Termii::new("Your API key");
+    let config = OtpConfig {
+        max_resends: 2,
+        ..OtpConfig::default()
+    };
+    let store = store_with(session(now() - 10 * 60, 0, 2));
+    let otp = OtpManager::new(&client.token, template(), store).with_config(config);
+
+    assert!(matches!(
+        otp.issue("user-42", "2348109077743").await,
+        Err(OtpError::ResendLimitReached(2))
+    ));
+    assert!(matches!(
+        otp.resend("user-42").await,
+        Err(OtpError::ResendLimitReached(2))
+    ));
+}
+
+#[tokio::test]
+async fn verify_refuses_once_attempts_are_used_up() {
+    let client = Termii::new("Your API key");
+    let otp = OtpManager::new(&client.token, template(), store_with(session(now(), 3, 0)));
+
+    assert_eq!(
+        otp.verify("user-42", "123456").await.unwrap(),
+        OtpOutcome::AttemptsExhausted
+    );
+    assert!(otp.store().get("user-42").unwrap().is_none());
+    assert!(matches!(
+        otp.verify("user-42", "123456").await,
+        Err(OtpError::NotIssued(_))
+    ));
+}
+
+#[tokio::test]
+async fn verify_refuses_expired_pins() {
+    let client = Termii::new("Your API key");
+    let mut expired = session(now() - 20 * 60, 0, 0);
+    expired.expires_at = now() - 10 * 60;
+    let otp = OtpManager::new(&client.token, template(), store_with(expired));
+
+    assert_eq!(
+        otp.verify("user-42", "123456").await.unwrap(),
+        OtpOutcome::Expired
+    );
+    assert!(otp.store().get("user-42").unwrap().is_none());
+}
+
+#[test]
+fn resends_walk_the_fallback_channels() {
+    let config = OtpConfig {
+        fallback_channels: vec![RequestTokenChannel::Whatsapp.into(), OtpDelivery::Voice],
+        ..OtpConfig::default()
+    };
+
+    let deliveries: Vec<OtpDelivery> = (0..4)
+        .map(|resends| config.delivery_for(&RequestTokenChannel::Dnd, resends))
+        .collect();
+
+    assert_eq!(
+        deliveries,
+        vec![
+            OtpDelivery::Message(RequestTokenChannel::Dnd),
+            OtpDelivery::Message(RequestTokenChannel::Whatsapp),
+            OtpDelivery::Voice,
+            OtpDelivery::Voice,
+        ]
+    );
+}
+
+#[test]
+fn resends_without_fallbacks_keep_the_template_channel() {
+    let config = OtpConfig::default();
+
+    assert_eq!(
+        config.delivery_for(&RequestTokenChannel::Generic, 2),
+        OtpDelivery::Message(RequestTokenChannel::Generic)
+    );
+}
+
+#[test]
+fn store_updates_see_each_other() {
+    let store = store_with(session(now(), 0, 0));
+
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                store
+                    .update("user-42", &mut |session| {
+                        let mut session = session?;
+                        thread::sleep(Duration::from_millis(1));
+                        session.attempts += 1;
+                        Some(session)
+                    })
+                    .unwrap();
+            });
+        }
+    });
+
+    assert_eq!(store.get("user-42").unwrap().unwrap().attempts, 8);
+}
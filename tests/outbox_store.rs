@@ -0,0 +1,132 @@
+//! The outbox store contract, checked against every store the crate ships.
+
+use termii_rust::common::{
+    errors::OutboxError,
+    outbox::{InMemoryOutboxStore, JobId, JobState, OutboxJob, OutboxResponse, OutboxStore},
+    switch::messaging::{Channel, MessageRequest, MessageType},
+};
+
+fn message(to: &str) -> MessageRequest {
+    MessageRequest::new(
+        to.to_string(),
+        "YourOrg".to_string(),
+        "Your message".to_string(),
+        MessageType::Plain,
+        Channel::Generic,
+    )
+}
+
+fn response() -> OutboxResponse {
+    OutboxResponse::Message(
+        serde_json::from_str(
+            r#"{"message_id": "9122821270554876574", "message": "Successfully Sent", "balance": 9, "user": "Peter Mcleish"}"#,
+        )
+        .unwrap(),
+    )
+}
+
+fn ids(jobs: Vec<OutboxJob>) -> Vec<JobId> {
+    jobs.into_iter().map(|job| job.id).collect()
+}
+
+fn check_due<S: OutboxStore>(store: S) {
+    let first = store.enqueue(message("2348000000001").into(), 0).unwrap();
+    let second = store.enqueue(message("2348000000002").into(), 0).unwrap();
+    let later = store.enqueue(message("2348000000003").into(), 100).unwrap();
+
+    assert_eq!(ids(store.due(0, 10).unwrap()), vec![first, second]);
+    assert_eq!(ids(store.due(0, 1).unwrap()), vec![first]);
+    assert_eq!(ids(store.due(100, 10).unwrap()), vec![first, second, later]);
+
+    store
+        .retry_at(first, 1, 200, "Service unavailable")
+        .unwrap();
+
+    assert_eq!(ids(store.due(100, 10).unwrap()), vec![second, later]);
+    assert_eq!(ids(store.due(200, 10).unwrap()), vec![first, second, later]);
+
+    let job = store.get(first).unwrap().unwrap();
+    assert_eq!(job.attempts, 1);
+    assert_eq!(job.next_attempt_at, 200);
+    assert_eq!(job.last_error.as_deref(), Some("Service unavailable"));
+    assert!(matches!(job.state, JobState::Pending));
+}
+
+fn check_finished_jobs<S: OutboxStore>(store: S) {
+    let sent = store.enqueue(message("2348000000001").into(), 0).unwrap();
+    let rejected = store.enqueue(message("2348000000002").into(), 0).unwrap();
+
+    store.mark_sent(sent, 1, response()).unwrap();
+    store.dead_letter(rejected, 1, "Invalid sender id").unwrap();
+
+    assert!(store.due(0, 10).unwrap().is_empty());
+    assert!(store.get(sent).unwrap().unwrap().response().is_some());
+    assert_eq!(ids(store.dead_letters().unwrap()), vec![rejected]);
+
+    assert_eq!(store.purge_sent().unwrap(), 1);
+    assert!(store.get(sent).unwrap().is_none());
+    assert!(store.get(rejected).unwrap().is_some());
+    assert_eq!(store.purge_sent().unwrap(), 0);
+}
+
+fn check_missing_job<S: OutboxStore>(store: S) {
+    assert!(store.get(JobId(42)).unwrap().is_none());
+    assert!(matches!(
+        store.mark_sent(JobId(42), 1, response()),
+        Err(OutboxError::JobNotFound(JobId(42)))
+    ));
+}
+
+#[test]
+fn in_memory_due() {
+    check_due(InMemoryOutboxStore::new());
+}
+
+#[test]
+fn in_memory_finished_jobs() {
+    check_finished_jobs(InMemoryOutboxStore::new());
+}
+
+#[test]
+fn in_memory_missing_job() {
+    check_missing_job(InMemoryOutboxStore::new());
+}
+
+#[cfg(feature = "outbox-sqlite")]
+mod sqlite {
+    use termii_rust::common::outbox::SqliteOutboxStore;
+
+    use super::*;
+
+    #[test]
+    fn due() {
+        check_due(SqliteOutboxStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn finished_jobs() {
+        check_finished_jobs(SqliteOutboxStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn missing_job() {
+        check_missing_job(SqliteOutboxStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn jobs_survive_reopening() {
+        let path = std::env::temp_dir().join(format!("termii-outbox-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let id = {
+            let store = SqliteOutboxStore::open(&path).unwrap();
+            store.enqueue(message("2348000000001").into(), 0).unwrap()
+        };
+
+        let store = SqliteOutboxStore::open(&path).unwrap();
+        assert_eq!(ids(store.due(0, 10).unwrap()), vec![id]);
+
+        drop(store);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,125 @@
+//! Response bodies as published in termii's API reference, kept in `tests/fixtures`.
+
+use termii_rust::common::{
+    insights::{
+        BalanceItem, DeliveryStatus, HistoryItem, LineType, LookupStatus, SearchItem, StatusItem,
+    },
+    money::{Currency, Decimal},
+    switch::{
+        campaign::PhoneBookListResponse,
+        messaging::{MessageBulkResponse, MessageResponse},
+        sender_id::{SenderIDItem, SenderIdStatus},
+    },
+    token::{InAppTokenResponse, RequestTokenResponse, VerificationOutcome},
+};
+
+fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path, err))
+}
+
+#[test]
+fn send_message() {
+    let response: MessageResponse = serde_json::from_str(&fixture("send_message.json")).unwrap();
+
+    assert_eq!(response.message_id, "9122821270554876574");
+    assert_eq!(response.balance.amount, Decimal::from(9));
+    assert_eq!(response.code, None);
+}
+
+#[test]
+fn send_bulk_message() {
+    let response: MessageBulkResponse =
+        serde_json::from_str(&fixture("send_bulk_message.json")).unwrap();
+
+    assert_eq!(response.code, "ok");
+    assert_eq!(response.message_id, "9122821270554876574");
+    assert_eq!(response.balance.amount, Decimal::from(9));
+}
+
+#[test]
+fn balance() {
+    let balance: BalanceItem = serde_json::from_str(&fixture("balance.json")).unwrap();
+
+    assert_eq!(balance.balance.amount, Decimal::ZERO);
+    assert_eq!(balance.balance.currency, Some(Currency::new("NGN")));
+}
+
+#[test]
+fn search() {
+    let item: SearchItem = serde_json::from_str(&fixture("search.json")).unwrap();
+
+    assert_eq!(item.number, "2348753243651");
+    assert_eq!(item.network_code, "62120");
+    assert!(item.dnd_active);
+}
+
+#[test]
+fn status() {
+    let status: StatusItem = serde_json::from_str(&fixture("status.json")).unwrap();
+
+    assert!(!status.is_ported());
+    assert_eq!(status.operator().unwrap().operator_name, "Airtel Nigeria");
+    assert_eq!(status.line_type(), Some(&LineType::Mobile));
+    assert_eq!(status.result[0].status, LookupStatus::Success);
+}
+
+#[test]
+fn history() {
+    let history: Vec<HistoryItem> = serde_json::from_str(&fixture("history.json")).unwrap();
+
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].message_id, "5508751839629937023");
+    assert_eq!(history[0].amount.amount, Decimal::ONE);
+    assert_eq!(history[0].delivery_status(), DeliveryStatus::Delivered);
+    assert_eq!(history[0].media_url, None);
+}
+
+#[test]
+fn request_token() {
+    let response: RequestTokenResponse =
+        serde_json::from_str(&fixture("request_token.json")).unwrap();
+
+    assert_eq!(response.pin_id, "29ae67c2-c8e1-4165-8a51-8d3d7c298081");
+    assert_eq!(response.to, "2348109077743");
+}
+
+#[test]
+fn verify_token() {
+    let outcome = VerificationOutcome::from_response_text(200, &fixture("verify_token.json"));
+
+    assert_eq!(
+        outcome,
+        Some(VerificationOutcome::Verified {
+            pin_id: "c8dcd048-5e7f-4347-8c89-4470c3af0b".to_string(),
+            msisdn: "2348109077743".to_string(),
+        })
+    );
+}
+
+#[test]
+fn in_app_token() {
+    let response: InAppTokenResponse = serde_json::from_str(&fixture("in_app_token.json")).unwrap();
+
+    assert_eq!(response.data.otp, "522726");
+    assert_eq!(response.data.phone_number, "2348109077743");
+}
+
+#[test]
+fn sender_ids() {
+    let page: serde_json::Value = serde_json::from_str(&fixture("sender_ids.json")).unwrap();
+    let items: Vec<SenderIDItem> = serde_json::from_value(page["data"].clone()).unwrap();
+
+    assert_eq!(items[0].sender_id, "ACME Key");
+    assert_eq!(items[0].status, SenderIdStatus::Active);
+    assert_eq!(items[0].country, None);
+}
+
+#[test]
+fn phonebooks() {
+    let response: PhoneBookListResponse =
+        serde_json::from_str(&fixture("phonebooks.json")).unwrap();
+
+    assert_eq!(response.data[0].total_number_of_contacts, 0);
+    assert_eq!(response.links.next, None);
+}
@@ -0,0 +1,94 @@
+//! Webhook signatures checked against digests computed outside the crate.
+
+use termii_rust::common::{
+    errors::WebhookError,
+    webhooks::{WebhookEvent, WebhookVerifier},
+};
+
+const SECRET: &str = "Your secret key";
+
+const BODY: &[u8] = br#"{"type": "inbound", "id": "1", "sender": "2347062668208", "receiver": "Termii", "message": "Hi"}"#;
+
+/// HMAC-SHA512 of `BODY` keyed with `SECRET`.
+const SIGNATURE: &str = "fa4bdd05dcff8b11eeb46b0ac1a232ef6042baa28008d2a50bf52d622683d1800f38fcf2187c62f18d89b286800c558793751e2b3bea066594e75e21a2af7fd8";
+
+#[test]
+fn accepts_the_body_signature() {
+    let verifier = WebhookVerifier::new(SECRET);
+
+    assert!(verifier.verify(Some(SIGNATURE), BODY).is_ok());
+    assert!(matches!(
+        verifier.parse(Some(SIGNATURE), BODY),
+        Ok(WebhookEvent::Inbound(_))
+    ));
+}
+
+#[test]
+fn accepts_any_hex_case_and_surrounding_whitespace() {
+    let verifier = WebhookVerifier::new(SECRET);
+
+    let upper = SIGNATURE.to_uppercase();
+    let mixed: String = SIGNATURE
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            if index % 2 == 0 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    assert!(verifier.verify(Some(&upper), BODY).is_ok());
+    assert!(verifier.verify(Some(&mixed), BODY).is_ok());
+    assert!(verifier
+        .verify(Some(&format!(" {}\n", SIGNATURE)), BODY)
+        .is_ok());
+}
+
+#[test]
+fn rejects_another_body_or_secret() {
+    let verifier = WebhookVerifier::new(SECRET);
+
+    assert!(matches!(
+        verifier.verify(Some(SIGNATURE), b"{}"),
+        Err(WebhookError::InvalidSignature)
+    ));
+    assert!(matches!(
+        WebhookVerifier::new("Another secret").verify(Some(SIGNATURE), BODY),
+        Err(WebhookError::InvalidSignature)
+    ));
+}
+
+#[test]
+fn rejects_malformed_signatures() {
+    let verifier = WebhookVerifier::new(SECRET);
+
+    let odd_length = &SIGNATURE[1..];
+    let not_hex = format!("zz{}", &SIGNATURE[2..]);
+    let truncated = &SIGNATURE[..64];
+
+    for signature in [odd_length, not_hex.as_str(), truncated] {
+        assert!(
+            matches!(
+                verifier.verify(Some(signature), BODY),
+                Err(WebhookError::InvalidSignature)
+            ),
+            "{}",
+            signature
+        );
+    }
+}
+
+#[test]
+fn requires_a_signature() {
+    let verifier = WebhookVerifier::new(SECRET);
+
+    for signature in [None, Some(""), Some("  ")] {
+        assert!(matches!(
+            verifier.verify(signature, BODY),
+            Err(WebhookError::MissingSignature)
+        ));
+    }
+}